@@ -44,4 +44,94 @@ fn main() {
         });
         println!("Xoodyak keyed      : {}", res.throughput(out.len() as _));
     }
+
+    {
+        let records = [[0u8; 64]; 256];
+        let inputs: Vec<&[u8]> = records.iter().map(|record| &record[..]).collect();
+        let mut outputs = [[0u8; 32]; 256];
+        let res = bench.run(options, || {
+            XoodyakHash::hash_many(&inputs, &mut outputs).unwrap();
+            outputs[0]
+        });
+        let len = records.len() * records[0].len();
+        println!("Xoodyak hash_many  : {}", res.throughput(len as _));
+    }
+
+    {
+        let msg = [0u8; 16384];
+        let mut st = XoodyakKeyed::new(b"key", None, None, None).unwrap();
+        let res = bench.run(options, || {
+            let mut out = [0u8; 16];
+            st.absorb(&msg);
+            st.squeeze(&mut out);
+            out
+        });
+        println!("Xoodyak keyed MAC  : {}", res.throughput(msg.len() as _));
+    }
+
+    {
+        let msg = [0u8; 16384];
+        let mut out = [0u8; 16384];
+        let mut st = XoodyakKeyed::new(b"key", None, None, None).unwrap();
+        let res = bench.run(options, || {
+            st.encrypt(&mut out, &msg).unwrap();
+            out[0]
+        });
+        println!("Xoodyak encrypt    : {}", res.throughput(msg.len() as _));
+    }
+
+    {
+        let msg = [0u8; 16384];
+        let mut out = [0u8; 16384];
+        let mut st = XoodyakKeyed::new(b"key", None, None, None).unwrap();
+        let res = bench.run(options, || {
+            st.decrypt(&mut out, &msg).unwrap();
+            out[0]
+        });
+        println!("Xoodyak decrypt    : {}", res.throughput(msg.len() as _));
+    }
+
+    {
+        let mut buf = [0u8; 16384];
+        let mut st = XoodyakKeyed::new(b"key", None, None, None).unwrap();
+        let res = bench.run(options, || {
+            st.encrypt_in_place(&mut buf);
+            buf[0]
+        });
+        println!("Xoodyak encrypt i/p: {}", res.throughput(buf.len() as _));
+    }
+
+    {
+        let msg = [0u8; 16384];
+        let mac = XoofffMac::new(b"key").unwrap();
+        let res = bench.run(options, || {
+            let mut st = mac.clone();
+            st.update(&msg);
+            st.finalize()
+        });
+        println!("Xoofff MAC         : {}", res.throughput(msg.len() as _));
+    }
+
+    // Up to 15 blocks are compressed one at a time, 16 blocks fill a multi-lane batch
+    {
+        let msg = [0u8; 15 * XOOFFF_BLOCK_BYTES];
+        let mac = XoofffMac::new(b"key").unwrap();
+        let res = bench.run(options, || {
+            let mut st = mac.clone();
+            st.update(&msg);
+            st.finalize()
+        });
+        println!("Xoofff MAC 1 lane  : {}", res.throughput(msg.len() as _));
+    }
+
+    {
+        let msg = [0u8; 16 * XOOFFF_BLOCK_BYTES];
+        let mac = XoofffMac::new(b"key").unwrap();
+        let res = bench.run(options, || {
+            let mut st = mac.clone();
+            st.update(&msg);
+            st.finalize()
+        });
+        println!("Xoofff MAC 16 lanes: {}", res.throughput(msg.len() as _));
+    }
 }
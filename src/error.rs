@@ -6,6 +6,11 @@ pub enum Error {
     InvalidParameterLength,
     KeyRequired,
     TagMismatch,
+    Finalized,
+    Desynchronized,
+    InvalidChunkSize,
+    InvalidChunkIndex,
+    UnsupportedBackend,
 }
 
 #[cfg(feature = "std")]
@@ -18,6 +23,11 @@ impl Display for Error {
             Error::InvalidParameterLength => write!(f, "Key too long"),
             Error::KeyRequired => write!(f, "A key is required"),
             Error::TagMismatch => write!(f, "Tag mismatch"),
+            Error::Finalized => write!(f, "Stream already finalized"),
+            Error::Desynchronized => write!(f, "Session desynchronized"),
+            Error::InvalidChunkSize => write!(f, "Invalid chunk size"),
+            Error::InvalidChunkIndex => write!(f, "Chunk index out of range"),
+            Error::UnsupportedBackend => write!(f, "Backend not supported on this CPU"),
         }
     }
 }
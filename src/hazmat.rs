@@ -0,0 +1,86 @@
+//! Low-level access to the Cyclist mode underlying Xoodyak.
+//!
+//! These functions expose the raw `Up`/`Down` calls and the domain
+//! separation colors. They are only safe to use when implementing a
+//! protocol that was designed and analyzed on top of Cyclist; a wrong
+//! color or rate silently breaks the security of every construction built
+//! on the same state.
+
+use crate::error::Error;
+use crate::permutation::Permutation;
+use crate::xoodoo::Xoodoo;
+use crate::xoodyak::internal;
+use crate::xoodyak::XoodyakCommon;
+
+pub use crate::xoodyak::internal::{Mode, Phase};
+
+pub const HASH_ABSORB_RATE: usize = <Xoodoo as Permutation>::HASH_ABSORB_RATE;
+pub const HASH_SQUEEZE_RATE: usize = <Xoodoo as Permutation>::HASH_SQUEEZE_RATE;
+pub const KEYED_ABSORB_RATE: usize = <Xoodoo as Permutation>::KEYED_ABSORB_RATE;
+pub const KEYED_SQUEEZE_RATE: usize = <Xoodoo as Permutation>::KEYED_SQUEEZE_RATE;
+pub const RATCHET_RATE: usize = <Xoodoo as Permutation>::RATCHET_RATE;
+
+pub trait Cyclist: XoodyakCommon {
+    fn mode(&self) -> Mode;
+    fn phase(&self) -> Phase;
+    fn absorb_rate(&self) -> usize;
+    fn squeeze_rate(&self) -> usize;
+    fn up(&mut self, out: Option<&mut [u8]>, cu: u8) -> Result<(), Error>;
+    fn down(&mut self, bin: Option<&[u8]>, cd: u8) -> Result<(), Error>;
+    fn absorb_any(&mut self, bin: &[u8], rate: usize, cd: u8) -> Result<(), Error>;
+    fn squeeze_any(&mut self, out: &mut [u8], cu: u8);
+}
+
+impl<T: XoodyakCommon> Cyclist for T {
+    #[inline]
+    fn mode(&self) -> Mode {
+        internal::XoodyakCommon::mode(self)
+    }
+
+    #[inline]
+    fn phase(&self) -> Phase {
+        internal::XoodyakCommon::phase(self)
+    }
+
+    #[inline]
+    fn absorb_rate(&self) -> usize {
+        internal::XoodyakCommon::absorb_rate(self)
+    }
+
+    #[inline]
+    fn squeeze_rate(&self) -> usize {
+        internal::XoodyakCommon::squeeze_rate(self)
+    }
+
+    #[inline]
+    fn up(&mut self, out: Option<&mut [u8]>, cu: u8) -> Result<(), Error> {
+        if out.as_ref().map(|x| x.len()).unwrap_or(0) > Cyclist::squeeze_rate(self) {
+            return Err(Error::InvalidBufferLength);
+        }
+        internal::XoodyakCommon::up(self, out, cu);
+        Ok(())
+    }
+
+    #[inline]
+    fn down(&mut self, bin: Option<&[u8]>, cd: u8) -> Result<(), Error> {
+        if bin.map(|x| x.len()).unwrap_or(0) > Cyclist::absorb_rate(self) {
+            return Err(Error::InvalidBufferLength);
+        }
+        internal::XoodyakCommon::down(self, bin, cd);
+        Ok(())
+    }
+
+    #[inline]
+    fn absorb_any(&mut self, bin: &[u8], rate: usize, cd: u8) -> Result<(), Error> {
+        if rate == 0 || rate > Cyclist::absorb_rate(self) {
+            return Err(Error::InvalidParameterLength);
+        }
+        internal::XoodyakCommon::absorb_any(self, bin, rate, cd);
+        Ok(())
+    }
+
+    #[inline]
+    fn squeeze_any(&mut self, out: &mut [u8], cu: u8) {
+        internal::XoodyakCommon::squeeze_any(self, out, cu)
+    }
+}
@@ -2,15 +2,44 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod error;
+#[cfg(feature = "hazmat")]
+pub mod hazmat;
+mod permutation;
 mod xoodoo;
 mod xoodyak;
+mod xoofff;
 
 pub use crate::error::Error as XoodyakError;
-pub use crate::xoodoo::Xoodoo;
+pub use crate::permutation::Permutation;
+pub use crate::xoodoo::{
+    Xoodoo, XoodooBackend, XoodooMulti, XoodooMultiBackend, XoodooStep, XoodooX16, XoodooX4,
+    XoodooX8, XOODOO_MAX_ROUNDS,
+};
 pub use crate::xoodyak::{
-    Tag as XoodyakTag, XoodyakAny, XoodyakCommon, XoodyakHash, XoodyakKeyed,
+    CyclistHash, CyclistKeyed, Tag as XoodyakTag, XoodyakAbsorber, XoodyakAny, XoodyakCommon,
+    XoodyakDecryptor, XoodyakEncryptor, XoodyakHash, XoodyakKeyed, XoodyakSession,
+    XoodyakStreamDecryptor, XoodyakStreamEncryptor, XoodyakStreamRandomAccess, XoodyakXofReader,
     AUTH_TAG_BYTES as XOODYAK_AUTH_TAG_BYTES,
+    STREAM_DEFAULT_CHUNK_BYTES as XOODYAK_STREAM_DEFAULT_CHUNK_BYTES,
+};
+#[cfg(feature = "std")]
+pub use crate::xoodyak::{
+    XoodyakDecryptReader, XoodyakEncryptWriter, XoodyakStreamReader, XoodyakStreamSeekReader,
+    XoodyakStreamWriter,
 };
+pub use crate::xoofff::{
+    Xoofff, XoofffMac, XoofffSane, XoofffSanse, XoofffWbc, XoofffWbcAe,
+    BLOCK_BYTES as XOOFFF_BLOCK_BYTES, MAX_KEY_BYTES as XOOFFF_MAX_KEY_BYTES,
+};
+
+#[cfg(feature = "aead")]
+pub use crate::xoodyak::XoodyakAead;
+#[cfg(feature = "digest")]
+pub use crate::xoodyak::XoodyakDigest;
+#[cfg(feature = "aead")]
+pub use aead;
+#[cfg(feature = "digest")]
+pub use digest;
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,38 @@
+pub(crate) const MAX_STATE_BYTES: usize = 200;
+
+pub trait Permutation: Clone + Default {
+    // At most 200 bytes, the width of Keccak-p[1600].
+    const STATE_BYTES: usize;
+    const HASH_ABSORB_RATE: usize;
+    const HASH_SQUEEZE_RATE: usize;
+    const KEYED_ABSORB_RATE: usize;
+    const KEYED_SQUEEZE_RATE: usize;
+    const RATCHET_RATE: usize;
+
+    fn permute(&mut self);
+    fn add_byte(&mut self, byte: u8, offset: usize);
+    fn add_bytes(&mut self, bytes: &[u8]);
+    fn extract_bytes(&mut self, out: &mut [u8]);
+
+    // Writes the rate bytes XORed with `bin` to `out` and leaves the result in the state.
+    fn encrypt_bytes(&mut self, out: &mut [u8], bin: &[u8]) {
+        let mut keystream = [0u8; MAX_STATE_BYTES];
+        let keystream = &mut keystream[..bin.len()];
+        self.extract_bytes(keystream);
+        self.add_bytes(bin);
+        for ((out_byte, keystream_byte), byte) in out.iter_mut().zip(keystream.iter()).zip(bin) {
+            *out_byte = keystream_byte ^ byte;
+        }
+    }
+
+    // Writes the rate bytes XORed with `bin` to `out` and leaves `bin` in the state.
+    fn decrypt_bytes(&mut self, out: &mut [u8], bin: &[u8]) {
+        let mut keystream = [0u8; MAX_STATE_BYTES];
+        let keystream = &mut keystream[..bin.len()];
+        self.extract_bytes(keystream);
+        for ((out_byte, keystream_byte), byte) in out.iter_mut().zip(keystream.iter()).zip(bin) {
+            *out_byte = keystream_byte ^ byte;
+        }
+        self.add_bytes(&out[..bin.len()]);
+    }
+}
@@ -22,8 +22,8 @@ fn test_unkeyed_empty() {
     assert_eq!(
         out,
         [
-            141, 216, 213, 137, 191, 252, 99, 169, 25, 45, 35, 27, 20, 160, 165, 255, 204, 246,
-            41, 214, 87, 39, 76, 114, 39, 130, 131, 52, 124, 189, 128, 53
+            141, 216, 213, 137, 191, 252, 99, 169, 25, 45, 35, 27, 20, 160, 165, 255, 204, 246, 41,
+            214, 87, 39, 76, 114, 39, 130, 131, 52, 124, 189, 128, 53
         ]
     );
 
@@ -73,8 +73,8 @@ fn test_encrypt() {
     assert_eq!(
         tag,
         [
-            10, 175, 140, 82, 142, 109, 23, 111, 201, 232, 32, 52, 122, 46, 254, 206, 236, 54,
-            97, 165, 40, 85, 166, 91, 124, 88, 26, 144, 100, 250, 243, 157
+            10, 175, 140, 82, 142, 109, 23, 111, 201, 232, 32, 52, 122, 46, 254, 206, 236, 54, 97,
+            165, 40, 85, 166, 91, 124, 88, 26, 144, 100, 250, 243, 157
         ]
     );
 }
@@ -89,8 +89,8 @@ fn test_unkeyed_hash() {
     assert_eq!(
         hash,
         [
-            144, 82, 141, 27, 59, 215, 34, 104, 197, 106, 251, 142, 112, 235, 111, 168, 19, 6,
-            112, 222, 160, 168, 230, 38, 27, 229, 248, 179, 94, 227, 247, 25
+            144, 82, 141, 27, 59, 215, 34, 104, 197, 106, 251, 142, 112, 235, 111, 168, 19, 6, 112,
+            222, 160, 168, 230, 38, 27, 229, 248, 179, 94, 227, 247, 25
         ]
     );
     st.absorb(&m[..]);
@@ -177,3 +177,1326 @@ fn test_aead_detached() {
         .unwrap();
     assert_eq!(&m2[..], &m[..]);
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn test_aead_with_ad() {
+    let mut msg = [0u8; 32];
+    for (i, x) in msg.iter_mut().enumerate() {
+        *x = i as u8;
+    }
+    let st0 = XoodyakKeyed::new_aead(&msg[..16], &msg[..16]).unwrap();
+
+    // Entries 1, 6, 34, 817 and 1089 of kat/aead_128_128.txt
+    let vectors = [
+        (0, 0, "4bf0e393144cb58069fc1febcafcfb3c"),
+        (0, 5, "7d75b567187f80938f306d439902ea03"),
+        (1, 0, "890788eac729d9539f401845b35a34d19f"),
+        (
+            24,
+            24,
+            "a0742552ff1cec7af3c48a03f61f4327be35d668eaa69e7c334b63e3f51d027a096e0a79df17d145",
+        ),
+        (
+            32,
+            32,
+            "dc56ec14215c53a5f2a2a5b957865f46f6201a071795a20ffa0116ad49de4de4007c270d39722ff5f3271700b1935b97",
+        ),
+    ];
+    for (pt_len, ad_len, ct) in vectors {
+        let (pt, ad, ct) = (&msg[..pt_len], &msg[..ad_len], kat_hex(ct));
+        let (c, tag) = ct.split_at(pt_len);
+
+        let mut st = st0.clone();
+        let mut out = [0u8; 32 + XOODYAK_AUTH_TAG_BYTES];
+        st.aead_encrypt_with_ad(&mut out[..ct.len()], Some(pt), Some(ad))
+            .unwrap();
+        assert_eq!(&out[..ct.len()], &ct[..]);
+
+        let mut st = st0.clone();
+        let mut out = [0u8; 32];
+        let auth_tag = st
+            .aead_encrypt_detached_with_ad(&mut out[..pt_len], Some(pt), Some(ad))
+            .unwrap();
+        assert_eq!(&out[..pt_len], c);
+        assert_eq!(auth_tag.as_ref(), tag);
+
+        let mut st = st0.clone();
+        let mut buf = [0u8; 32];
+        buf[..pt_len].copy_from_slice(pt);
+        let auth_tag = st.aead_encrypt_in_place_detached_with_ad(&mut buf[..pt_len], Some(ad));
+        assert_eq!(&buf[..pt_len], c);
+        assert_eq!(auth_tag.as_ref(), tag);
+
+        let mut st = st0.clone();
+        let mut out = [0u8; 32];
+        st.aead_decrypt_with_ad(&mut out[..pt_len], &ct, Some(ad))
+            .unwrap();
+        assert_eq!(&out[..pt_len], pt);
+
+        let mut st = st0.clone();
+        let mut out = [0u8; 32];
+        st.aead_decrypt_detached_with_ad(&mut out[..pt_len], &auth_tag, Some(c), Some(ad))
+            .unwrap();
+        assert_eq!(&out[..pt_len], pt);
+
+        let mut st = st0.clone();
+        let mut buf = [0u8; 32];
+        buf[..pt_len].copy_from_slice(c);
+        st.aead_decrypt_in_place_detached_with_ad(&mut buf[..pt_len], &auth_tag, Some(ad))
+            .unwrap();
+        assert_eq!(&buf[..pt_len], pt);
+
+        let mut st = st0.clone();
+        let mut out = [0u8; 32];
+        assert!(st
+            .aead_decrypt_with_ad(&mut out[..pt_len], &ct, Some(b"AD"))
+            .is_err());
+    }
+
+    // Without *_with_ad, empty messages keep the output of earlier releases
+    let mut st = st0.clone();
+    let mut c = [0u8; XOODYAK_AUTH_TAG_BYTES];
+    st.aead_encrypt(&mut c, None).unwrap();
+    assert_eq!(c[..], kat_hex("7b6058c156a1b33973b790ed8926ebe4")[..]);
+
+    let mut st = st0;
+    st.absorb(&msg[..5]);
+    let mut c = [0u8; XOODYAK_AUTH_TAG_BYTES];
+    st.aead_encrypt(&mut c, None).unwrap();
+    assert_eq!(c[..], kat_hex("45f808c319abc4d06c11ff7df1c59a91")[..]);
+}
+
+#[cfg(feature = "std")]
+fn kat_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[cfg(feature = "std")]
+fn kat_entries(kat: &str) -> Vec<std::collections::HashMap<&str, &str>> {
+    kat.split("\n\n")
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            entry
+                .lines()
+                .map(|line| {
+                    let mut parts = line.splitn(2, '=');
+                    let name = parts.next().unwrap().trim();
+                    let value = parts.next().expect("invalid KAT line").trim();
+                    (name, value)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_aead_kat() {
+    let entries = kat_entries(include_str!("../kat/aead_128_128.txt"));
+    assert_eq!(entries.len(), 33 * 33);
+    for entry in entries {
+        let key = kat_hex(entry["Key"]);
+        let nonce = kat_hex(entry["Nonce"]);
+        let pt = kat_hex(entry["PT"]);
+        let ad = kat_hex(entry["AD"]);
+        let ct = kat_hex(entry["CT"]);
+
+        let mut st = XoodyakKeyed::new_aead(&key, &nonce).unwrap();
+        let st0 = st.clone();
+        let c = st
+            .aead_encrypt_to_vec_with_ad(Some(&pt), Some(&ad))
+            .unwrap();
+        assert_eq!(c, ct, "Count = {}", entry["Count"]);
+
+        let mut st = st0.clone();
+        let mut buf = pt.clone();
+        buf.resize(pt.len() + XOODYAK_AUTH_TAG_BYTES, 0);
+        st.aead_encrypt_in_place_with_ad(&mut buf, Some(&ad))
+            .unwrap();
+        assert_eq!(buf, ct, "Count = {}", entry["Count"]);
+
+        let mut st = st0.clone();
+        let m = st.aead_decrypt_to_vec_with_ad(&ct, Some(&ad)).unwrap();
+        assert_eq!(m, pt, "Count = {}", entry["Count"]);
+
+        let mut st = st0;
+        let mut tampered = ct.clone();
+        tampered[0] ^= 1;
+        assert!(st
+            .aead_decrypt_to_vec_with_ad(&tampered, Some(&ad))
+            .is_err());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_hash_kat() {
+    let entries = kat_entries(include_str!("../kat/hash_256.txt"));
+    assert_eq!(entries.len(), 1025);
+    for entry in entries {
+        let msg = kat_hex(entry["Msg"]);
+        let md = kat_hex(entry["MD"]);
+
+        let mut st = XoodyakHash::new();
+        st.absorb(&msg);
+        let mut out = [0u8; 32];
+        st.squeeze(&mut out);
+        assert_eq!(&out[..], &md[..], "Count = {}", entry["Count"]);
+    }
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn test_digest() {
+    use digest::{Digest, ExtendableOutput, Update, XofReader};
+
+    let md = XoodyakDigest::digest(b"");
+    assert_eq!(
+        md[..],
+        [
+            234, 21, 47, 43, 71, 188, 226, 78, 251, 102, 196, 121, 212, 173, 241, 123, 211, 36,
+            216, 6, 232, 95, 247, 94, 227, 105, 238, 80, 220, 143, 139, 209
+        ]
+    );
+
+    let m = b"Lorem Ipsum is simply dummy text of the printing and typesetting industry.";
+    let mut st = XoodyakHash::new();
+    st.absorb(m);
+    let mut expected = [0u8; 100];
+    st.squeeze(&mut expected);
+
+    let mut hasher = XoodyakDigest::new();
+    Digest::update(&mut hasher, m);
+    assert_eq!(hasher.finalize_reset()[..], expected[..32]);
+    Digest::update(&mut hasher, m);
+    assert_eq!(hasher.finalize()[..], expected[..32]);
+
+    for split in [0, 1, 15, 16, 17, 32, 40, m.len()] {
+        let mut hasher = XoodyakDigest::new();
+        Digest::update(&mut hasher, b"");
+        Digest::update(&mut hasher, &m[..split]);
+        Digest::update(&mut hasher, b"");
+        for chunk in m[split..].chunks(3) {
+            Digest::update(&mut hasher, chunk);
+        }
+        assert_eq!(hasher.finalize()[..], expected[..32], "split = {}", split);
+    }
+    let mut hasher = XoodyakDigest::new();
+    Digest::update(&mut hasher, b"");
+    Digest::update(&mut hasher, b"");
+    assert_eq!(hasher.finalize(), XoodyakDigest::digest(b""));
+
+    let mut hasher = XoodyakDigest::default();
+    Update::update(&mut hasher, &m[..20]);
+    Update::update(&mut hasher, &m[20..]);
+    let mut reader = hasher.finalize_xof();
+    let mut out = [0u8; 100];
+    for chunk in out.chunks_mut(7) {
+        XofReader::read(&mut reader, chunk);
+    }
+    assert_eq!(out, expected);
+}
+
+#[cfg(all(feature = "aead", feature = "std"))]
+#[test]
+fn test_aead_traits() {
+    use aead::{AeadInPlace, KeyInit};
+
+    let entries = kat_entries(include_str!("../kat/aead_128_128.txt"));
+    for entry in entries.iter().step_by(7) {
+        let key = kat_hex(entry["Key"]);
+        let nonce = kat_hex(entry["Nonce"]);
+        let pt = kat_hex(entry["PT"]);
+        let ad = kat_hex(entry["AD"]);
+        let ct = kat_hex(entry["CT"]);
+
+        let cipher = XoodyakAead::new_from_slice(&key).unwrap();
+        let mut buf = pt.clone();
+        let tag = cipher
+            .encrypt_in_place_detached(nonce[..].into(), &ad, &mut buf)
+            .unwrap();
+        assert_eq!(&buf[..], &ct[..pt.len()]);
+        assert_eq!(&tag[..], &ct[pt.len()..]);
+
+        cipher
+            .decrypt_in_place_detached(nonce[..].into(), &ad, &mut buf, &tag)
+            .unwrap();
+        assert_eq!(buf, pt);
+
+        let mut bad_tag = tag;
+        bad_tag[0] ^= 1;
+        assert!(cipher
+            .decrypt_in_place_detached(nonce[..].into(), &ad, &mut buf, &bad_tag)
+            .is_err());
+    }
+}
+
+#[test]
+fn test_absorber() {
+    let mut m = [0u8; 200];
+    for (i, x) in m.iter_mut().enumerate() {
+        *x = (i * 7) as u8;
+    }
+    for len in [0, 1, 15, 16, 17, 32, 43, 44, 45, 88, 100, 200] {
+        let m = &m[..len];
+
+        let mut st = XoodyakHash::new();
+        st.absorb(m);
+        let mut expected = [0u8; 32];
+        st.squeeze(&mut expected);
+
+        let mut st = XoodyakKeyed::new(b"key", None, None, None).unwrap();
+        st.absorb(m);
+        let mut expected_keyed = [0u8; 32];
+        st.squeeze(&mut expected_keyed);
+
+        for split in [1, 3, 16, 17, 44, 50] {
+            let mut absorber = XoodyakAbsorber::<XoodyakHash>::default();
+            for chunk in m.chunks(split) {
+                absorber.update(chunk);
+                absorber.update(&[]);
+            }
+            let mut out = [0u8; 32];
+            absorber.finalize().squeeze(&mut out);
+            assert_eq!(out, expected);
+
+            let st = XoodyakKeyed::new(b"key", None, None, None).unwrap();
+            let mut absorber = XoodyakAbsorber::new(st);
+            for chunk in m.chunks(split) {
+                absorber.update(chunk);
+            }
+            let mut out = [0u8; 32];
+            absorber.finalize().squeeze(&mut out);
+            assert_eq!(out, expected_keyed);
+        }
+    }
+}
+
+#[test]
+fn test_encryptor_decryptor() {
+    let mut m = [0u8; 100];
+    for (i, x) in m.iter_mut().enumerate() {
+        *x = i as u8;
+    }
+    let st0 = XoodyakKeyed::new(b"key", Some(b"nonce"), None, None).unwrap();
+    for len in [0, 1, 23, 24, 25, 48, 50, 100] {
+        let m = &m[..len];
+        let mut st = st0.clone();
+        let mut expected = [0u8; 100];
+        let expected_tag = st.aead_encrypt_detached(&mut expected, Some(m)).unwrap();
+        let expected = &expected[..len];
+
+        for split in [1, 5, 23, 24, 25, 100] {
+            let mut encryptor = XoodyakEncryptor::new(st0.clone());
+            let mut c = [0u8; 100];
+            for (c_chunk, m_chunk) in c.chunks_mut(split).zip(m.chunks(split)) {
+                encryptor.update(c_chunk, m_chunk).unwrap();
+            }
+            let tag = encryptor.finalize();
+            assert_eq!(&c[..len], expected);
+            assert_eq!(tag, expected_tag);
+
+            let mut decryptor = XoodyakDecryptor::new(st0.clone());
+            let mut buf = [0u8; 100];
+            buf[..len].copy_from_slice(&c[..len]);
+            for chunk in buf[..len].chunks_mut(split) {
+                decryptor.update_in_place(chunk);
+            }
+            decryptor.clone().finalize(&tag).unwrap();
+            assert_eq!(&buf[..len], m);
+            assert!(decryptor.finalize(&XoodyakTag::default()).is_err());
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_io_adapters() {
+    use std::io::{self, Read};
+
+    let m: Vec<u8> = (0..10000).map(|i| i as u8).collect();
+
+    let mut absorber = XoodyakAbsorber::new(XoodyakHash::new());
+    io::copy(&mut &m[..], &mut absorber).unwrap();
+    let mut xof = XoodyakXofReader::new(absorber.finalize());
+    let mut out = Vec::new();
+    (&mut xof).take(100).read_to_end(&mut out).unwrap();
+    let mut st = XoodyakHash::new();
+    st.absorb(&m);
+    assert_eq!(out, st.squeeze_to_vec(100));
+
+    let st0 = XoodyakKeyed::new(b"key", Some(b"nonce"), None, None).unwrap();
+    let mut writer = XoodyakEncryptWriter::new(Vec::new(), st0.clone());
+    io::copy(&mut &m[..], &mut writer).unwrap();
+    let c = writer.finalize().unwrap();
+    let mut st = st0.clone();
+    assert_eq!(c, st.aead_encrypt_to_vec(Some(&m)).unwrap());
+
+    struct FailingWriter;
+    impl io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut writer = XoodyakEncryptWriter::new(FailingWriter, st0.clone());
+    let err = io::Write::write(&mut writer, &m[..10]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    assert!(writer.is_desynchronized());
+    let err = io::Write::write(&mut writer, &m[..10]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    assert!(writer.finalize().is_err());
+
+    let mut reader = XoodyakDecryptReader::new(&c[..], st0.clone());
+    let mut m2 = Vec::new();
+    reader.read_to_end(&mut m2).unwrap();
+    assert_eq!(m2, m);
+
+    let mut c2 = c.clone();
+    *c2.last_mut().unwrap() ^= 1;
+    let mut reader = XoodyakDecryptReader::new(&c2[..], st0.clone());
+    let mut m2 = Vec::new();
+    let err = reader.read_to_end(&mut m2).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let err = reader.read(&mut [0u8; 16]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let mut reader = XoodyakDecryptReader::new(&c[..10], st0);
+    let mut m2 = Vec::new();
+    let err = reader.read_to_end(&mut m2).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    let err = reader.read(&mut [0u8; 16]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_stream_chunks() {
+    let key = b"key";
+    let nonce = b"nonce";
+    let mut enc = XoodyakStreamEncryptor::new(key, nonce, Some(b"ad"), 10).unwrap();
+    let mut c0 = [0u8; 10 + XOODYAK_AUTH_TAG_BYTES];
+    c0[..10].copy_from_slice(b"0123456789");
+    let mut c1 = [0u8; 4 + XOODYAK_AUTH_TAG_BYTES];
+    c1[..4].copy_from_slice(b"abcd");
+    assert!(enc
+        .seal_chunk(&mut [0u8; 5 + XOODYAK_AUTH_TAG_BYTES], false)
+        .is_err());
+    enc.seal_chunk(&mut c0, false).unwrap();
+    enc.seal_chunk(&mut c1, true).unwrap();
+    assert_eq!(
+        enc.seal_chunk(&mut [0u8; XOODYAK_AUTH_TAG_BYTES], true),
+        Err(XoodyakError::Finalized)
+    );
+
+    let dec0 = XoodyakStreamDecryptor::new(key, nonce, Some(b"ad"), 10).unwrap();
+
+    let mut dec = dec0.clone();
+    let mut buf = c0;
+    assert_eq!(dec.open_chunk(&mut buf, false).unwrap(), b"0123456789");
+    let mut buf = c1;
+    assert_eq!(dec.open_chunk(&mut buf, true).unwrap(), b"abcd");
+    assert!(dec.is_finished());
+
+    let mut dec = dec0.clone();
+    let mut buf = c1;
+    assert!(dec.open_chunk(&mut buf, true).is_err());
+    assert_eq!(buf[..4], [0u8; 4]);
+
+    let mut dec = dec0.clone();
+    let mut buf = c0;
+    assert!(dec.open_chunk(&mut buf, true).is_err());
+
+    let mut dec = XoodyakStreamDecryptor::new(key, nonce, None, 10).unwrap();
+    let mut buf = c0;
+    assert!(dec.open_chunk(&mut buf, false).is_err());
+
+    assert_eq!(
+        XoodyakStreamEncryptor::new(key, nonce, None, 0).unwrap_err(),
+        XoodyakError::InvalidChunkSize
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_stream_io() {
+    use std::io::{self, Read, Write};
+
+    let key = b"key";
+    let nonce = b"nonce";
+    for len in [0usize, 1, 99, 100, 101, 1000] {
+        let m: Vec<u8> = (0..len).map(|i| i as u8).collect();
+        let enc = XoodyakStreamEncryptor::new(key, nonce, None, 100).unwrap();
+        let mut writer = XoodyakStreamWriter::new(Vec::new(), enc);
+        for chunk in m.chunks(7) {
+            writer.write_all(chunk).unwrap();
+        }
+        let c = writer.finalize().unwrap();
+        let chunks = std::cmp::max(1, len.div_ceil(100));
+        assert_eq!(c.len(), len + chunks * XOODYAK_AUTH_TAG_BYTES);
+
+        let dec = XoodyakStreamDecryptor::new(key, nonce, None, 100).unwrap();
+        let mut reader = XoodyakStreamReader::new(&c[..], dec);
+        let mut m2 = Vec::new();
+        reader.read_to_end(&mut m2).unwrap();
+        assert_eq!(m2, m);
+
+        if len > 100 {
+            let dec = XoodyakStreamDecryptor::new(key, nonce, None, 100).unwrap();
+            let truncated = &c[..100 + XOODYAK_AUTH_TAG_BYTES];
+            let mut reader = XoodyakStreamReader::new(truncated, dec);
+            let mut m2 = Vec::new();
+            let err = reader.read_to_end(&mut m2).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert!(m2.is_empty());
+
+            let mut c2 = c.clone();
+            c2[100 + XOODYAK_AUTH_TAG_BYTES] ^= 1;
+            let dec = XoodyakStreamDecryptor::new(key, nonce, None, 100).unwrap();
+            let mut reader = XoodyakStreamReader::new(&c2[..], dec);
+            let mut m2 = Vec::new();
+            assert!(reader.read_to_end(&mut m2).is_err());
+            assert_eq!(m2, &m[..100]);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_stream_random_access() {
+    use std::io::{Cursor, Write};
+
+    let key = b"key";
+    let nonce = b"nonce";
+    for len in [0usize, 1, 99, 100, 101, 1000, 1050] {
+        let m: Vec<u8> = (0..len).map(|i| (i * 3) as u8).collect();
+        let enc = XoodyakStreamEncryptor::new(key, nonce, Some(b"ad"), 100).unwrap();
+        let mut writer = XoodyakStreamWriter::new(Vec::new(), enc);
+        writer.write_all(&m).unwrap();
+        let c = writer.finalize().unwrap();
+
+        let access =
+            XoodyakStreamRandomAccess::new(key, nonce, Some(b"ad"), 100, c.len() as u64).unwrap();
+        let last = access.chunk_count() - 1;
+        assert!(access.chunk_ciphertext_range(last).is_ok());
+        assert_eq!(
+            access.chunk_ciphertext_range(last + 1).unwrap_err(),
+            XoodyakError::InvalidChunkIndex
+        );
+
+        let mut reader =
+            XoodyakStreamSeekReader::new(Cursor::new(&c), key, nonce, Some(b"ad"), 100).unwrap();
+        assert_eq!(reader.plaintext_len(), len as u64);
+        for (offset, size) in [
+            (0, 10),
+            (95, 10),
+            (0, 2000),
+            (250, 500),
+            (999, 3),
+            (1049, 5),
+        ] {
+            let mut buf = vec![0u8; size];
+            let n = reader.read_at(offset as u64, &mut buf).unwrap();
+            let expected = &m[offset.min(len)..(offset + size).min(len)];
+            assert_eq!(&buf[..n], expected);
+        }
+
+        if len > 300 {
+            let mut c2 = c.clone();
+            c2[2 * (100 + XOODYAK_AUTH_TAG_BYTES) + 1] ^= 1;
+            let mut reader =
+                XoodyakStreamSeekReader::new(Cursor::new(&c2), key, nonce, Some(b"ad"), 100)
+                    .unwrap();
+            let mut buf = [0u8; 10];
+            assert_eq!(reader.read_at(100, &mut buf).unwrap(), 10);
+            assert!(reader.read_at(200, &mut buf).is_err());
+
+            let truncated = &c[..3 * (100 + XOODYAK_AUTH_TAG_BYTES)];
+            let mut reader =
+                XoodyakStreamSeekReader::new(Cursor::new(truncated), key, nonce, Some(b"ad"), 100)
+                    .unwrap();
+            assert!(reader.read_at(250, &mut buf).is_err());
+        }
+    }
+}
+
+#[test]
+fn test_session() {
+    let st = XoodyakKeyed::new(b"key", Some(b"nonce"), None, None).unwrap();
+    let mut alice = XoodyakSession::new(st.clone());
+    let mut bob = XoodyakSession::new(st);
+
+    let mut c1 = [0u8; 5 + XOODYAK_AUTH_TAG_BYTES];
+    alice.seal(&mut c1, Some(b"ad1"), b"hello").unwrap();
+    let mut c2 = [0u8; 5 + XOODYAK_AUTH_TAG_BYTES];
+    alice.seal(&mut c2, None, b"world").unwrap();
+    assert_ne!(c1[..5], c2[..5]);
+
+    let mut m = [0u8; 5];
+    bob.open(&mut m, Some(b"ad1"), &c1).unwrap();
+    assert_eq!(&m, b"hello");
+    let mut buf = c2;
+    assert_eq!(bob.open_in_place(&mut buf, None).unwrap(), b"world");
+
+    let mut c3 = [0u8; 5 + XOODYAK_AUTH_TAG_BYTES];
+    c3[..5].copy_from_slice(b"reply");
+    bob.seal_in_place(&mut c3, None).unwrap();
+    alice.open(&mut m, None, &c3).unwrap();
+    assert_eq!(&m, b"reply");
+    assert_eq!(alice.messages(), 3);
+    assert_eq!(bob.messages(), 3);
+
+    let mut c4 = [0u8; 5 + XOODYAK_AUTH_TAG_BYTES];
+    alice.seal(&mut c4, None, b"first").unwrap();
+    let mut c5 = [0u8; 6 + XOODYAK_AUTH_TAG_BYTES];
+    alice.seal(&mut c5, None, b"second").unwrap();
+    let mut m = [0u8; 6];
+    assert_eq!(bob.open(&mut m, None, &c5), Err(XoodyakError::TagMismatch));
+    assert!(bob.is_desynchronized());
+    assert_eq!(
+        bob.open(&mut m[..5], None, &c4),
+        Err(XoodyakError::Desynchronized)
+    );
+    assert_eq!(
+        bob.seal(&mut c4, None, b"again"),
+        Err(XoodyakError::Desynchronized)
+    );
+}
+
+#[cfg(feature = "hazmat")]
+#[test]
+fn test_hazmat() {
+    use crate::hazmat::*;
+
+    let m = b"Lorem Ipsum is simply dummy text of the printing and typesetting industry.";
+    let mut st = XoodyakHash::new();
+    st.absorb(m);
+    let mut expected = [0u8; 32];
+    st.squeeze(&mut expected);
+
+    let mut st = XoodyakHash::new();
+    assert_eq!(st.mode(), Mode::Hash);
+    assert_eq!(Cyclist::absorb_rate(&st), HASH_ABSORB_RATE);
+    st.absorb_any(m, HASH_ABSORB_RATE, 0x03).unwrap();
+    assert_eq!(st.phase(), Phase::Down);
+    let mut out = [0u8; 32];
+    st.squeeze_any(&mut out, 0x40);
+    assert_eq!(out, expected);
+
+    let mut st = XoodyakKeyed::new(b"key", None, None, None).unwrap();
+    assert_eq!(st.mode(), Mode::Keyed);
+    assert!(st.absorb_any(m, KEYED_ABSORB_RATE + 1, 0x03).is_err());
+    assert!(st.down(Some(&[0u8; KEYED_ABSORB_RATE + 1]), 0x00).is_err());
+    assert!(st
+        .up(Some(&mut [0u8; KEYED_SQUEEZE_RATE + 1]), 0x00)
+        .is_err());
+    st.absorb_any(m, KEYED_ABSORB_RATE, 0x07).unwrap();
+    let mut block = [0u8; KEYED_SQUEEZE_RATE];
+    st.up(Some(&mut block), 0x09).unwrap();
+    st.down(None, 0x00).unwrap();
+    assert_eq!(st.phase(), Phase::Down);
+}
+
+#[derive(Clone, Debug, Default)]
+struct DoubleXoodoo(Xoodoo);
+
+impl Permutation for DoubleXoodoo {
+    const STATE_BYTES: usize = 48;
+    const HASH_ABSORB_RATE: usize = 16;
+    const HASH_SQUEEZE_RATE: usize = 16;
+    const KEYED_ABSORB_RATE: usize = 44;
+    const KEYED_SQUEEZE_RATE: usize = 24;
+    const RATCHET_RATE: usize = 16;
+
+    fn permute(&mut self) {
+        self.0.permute();
+        self.0.permute();
+    }
+
+    fn add_byte(&mut self, byte: u8, offset: usize) {
+        self.0.add_byte(byte, offset)
+    }
+
+    fn add_bytes(&mut self, bytes: &[u8]) {
+        self.0.add_bytes(bytes)
+    }
+
+    fn extract_bytes(&mut self, out: &mut [u8]) {
+        self.0.extract_bytes(out)
+    }
+}
+
+#[test]
+fn test_generic_permutation() {
+    let m = b"message";
+
+    let mut st = XoodyakHash::new();
+    st.absorb(m);
+    let mut expected = [0u8; 32];
+    st.squeeze(&mut expected);
+
+    let mut st = CyclistHash::<Xoodoo>::new();
+    st.absorb(m);
+    let mut out = [0u8; 32];
+    st.squeeze(&mut out);
+    assert_eq!(out, expected);
+
+    let mut st = CyclistHash::<DoubleXoodoo>::new();
+    st.absorb(m);
+    st.squeeze(&mut out);
+    assert_ne!(out, expected);
+
+    let st0 = CyclistKeyed::<DoubleXoodoo>::new(b"key", Some(b"nonce"), None, None).unwrap();
+    let mut st = st0.clone();
+    let mut c = [0u8; 7 + XOODYAK_AUTH_TAG_BYTES];
+    st.aead_encrypt_with_ad(&mut c, Some(m), Some(b"ad"))
+        .unwrap();
+    let mut st = XoodyakKeyed::new(b"key", Some(b"nonce"), None, None).unwrap();
+    let mut c2 = [0u8; 7 + XOODYAK_AUTH_TAG_BYTES];
+    st.aead_encrypt_with_ad(&mut c2, Some(m), Some(b"ad"))
+        .unwrap();
+    assert_ne!(c, c2);
+    let mut st = st0;
+    st.ratchet();
+    let mut st2 = st.clone();
+    let mut m2 = [0u8; 7];
+    st.encrypt_in_place(&mut m2);
+    st2.decrypt_in_place(&mut m2);
+    assert_eq!(m2, [0u8; 7]);
+}
+
+#[derive(Clone, Debug, Default)]
+struct DefaultCryptXoodoo(Xoodoo);
+
+impl Permutation for DefaultCryptXoodoo {
+    const STATE_BYTES: usize = 48;
+    const HASH_ABSORB_RATE: usize = 16;
+    const HASH_SQUEEZE_RATE: usize = 16;
+    const KEYED_ABSORB_RATE: usize = 44;
+    const KEYED_SQUEEZE_RATE: usize = 24;
+    const RATCHET_RATE: usize = 16;
+
+    fn permute(&mut self) {
+        self.0.permute();
+    }
+
+    fn add_byte(&mut self, byte: u8, offset: usize) {
+        self.0.add_byte(byte, offset)
+    }
+
+    fn add_bytes(&mut self, bytes: &[u8]) {
+        self.0.add_bytes(bytes)
+    }
+
+    fn extract_bytes(&mut self, out: &mut [u8]) {
+        self.0.extract_bytes(out)
+    }
+}
+
+#[test]
+fn test_crypt_blocks() {
+    let mut m = [0u8; 100];
+    for (i, b) in m.iter_mut().enumerate() {
+        *b = (i as u8).wrapping_mul(0x1f) ^ 0x6b;
+    }
+    for len in 0..=m.len() {
+        let m = &m[..len];
+        let mut st = XoodyakKeyed::new(b"key", Some(b"nonce"), None, None).unwrap();
+        let mut st2 =
+            CyclistKeyed::<DefaultCryptXoodoo>::new(b"key", Some(b"nonce"), None, None).unwrap();
+        st.absorb(m);
+        st2.absorb(m);
+
+        let mut c = [0u8; 100];
+        let mut c2 = [0u8; 100];
+        st.encrypt(&mut c, m).unwrap();
+        st2.encrypt(&mut c2, m).unwrap();
+        assert_eq!(c[..len], c2[..len]);
+        let mut in_place = [0u8; 100];
+        in_place[..len].copy_from_slice(m);
+        let mut st3 = XoodyakKeyed::new(b"key", Some(b"nonce"), None, None).unwrap();
+        st3.absorb(m);
+        st3.encrypt_in_place(&mut in_place[..len]);
+        assert_eq!(in_place[..len], c[..len]);
+
+        let mut p = [0u8; 100];
+        let mut p2 = [0u8; 100];
+        st.decrypt(&mut p, &c[..len]).unwrap();
+        st2.decrypt(&mut p2, &c[..len]).unwrap();
+        assert_eq!(p[..len], p2[..len]);
+        st3.decrypt_in_place(&mut in_place[..len]);
+        assert_eq!(in_place[..len], p[..len]);
+
+        let mut tag = [0u8; 16];
+        let mut tag2 = [0u8; 16];
+        let mut tag3 = [0u8; 16];
+        st.squeeze(&mut tag);
+        st2.squeeze(&mut tag2);
+        st3.squeeze(&mut tag3);
+        assert_eq!(tag, tag2);
+        assert_eq!(tag, tag3);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_hash_many() {
+    let mut data = [0u8; 200];
+    for (i, b) in data.iter_mut().enumerate() {
+        *b = (i as u8).wrapping_mul(0x2d) ^ 0x91;
+    }
+    let inputs: Vec<&[u8]> = (0..100)
+        .map(|i| &data[i % 7..i % 7 + (i * 37) % 150])
+        .collect();
+    let mut outputs = vec![[0u8; 32]; inputs.len()];
+    XoodyakHash::hash_many(&inputs, &mut outputs).unwrap();
+    for (input, output) in inputs.iter().zip(outputs.iter()) {
+        let mut st = XoodyakHash::new();
+        st.absorb(input);
+        let mut expected = [0u8; 32];
+        st.squeeze(&mut expected);
+        assert_eq!(*output, expected);
+    }
+
+    XoodyakHash::hash_many(&[], &mut []).unwrap();
+    let mut output = [[0u8; 32]; 1];
+    XoodyakHash::hash_many(&[b""], &mut output).unwrap();
+    let mut st = XoodyakHash::new();
+    st.absorb(b"");
+    let mut expected = [0u8; 32];
+    st.squeeze(&mut expected);
+    assert_eq!(output[0], expected);
+    assert_eq!(
+        XoodyakHash::hash_many(&inputs, &mut outputs[..1]),
+        Err(XoodyakError::InvalidBufferLength)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_permute_rounds() {
+    assert_eq!(
+        crate::xoodoo::ROUND_KEYS,
+        [0x058, 0x038, 0x3c0, 0x0d0, 0x120, 0x014, 0x060, 0x02c, 0x380, 0x0f0, 0x1a0, 0x012]
+    );
+
+    let mut bytes = [0u8; 48];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    let mut out = [0u8; 48];
+    let mut expected = [0u8; 48];
+
+    let mut st = Xoodoo::from_bytes(bytes);
+    st.permute_rounds(0);
+    st.bytes(&mut out);
+    assert_eq!(out, bytes);
+
+    let mut st = Xoodoo::from_bytes(bytes);
+    st.permute_rounds(XOODOO_MAX_ROUNDS);
+    st.bytes(&mut out);
+    let mut st = Xoodoo::from_bytes(bytes);
+    st.permute();
+    st.bytes(&mut expected);
+    assert_eq!(out, expected);
+
+    let mut st = Xoodoo::from_bytes(bytes);
+    st.permute_rounds(1);
+    st.bytes(&mut out);
+    assert_eq!(
+        out[..],
+        kat_hex("52ebc26bef6969ed42a28260e52821eeaf3b2fbbbd6135ed393f393b2f65236d9199070f2823bcb7c0d8565e7279e6ed")[..]
+    );
+
+    let mut st = Xoodoo::from_bytes(bytes);
+    st.permute_rounds(6);
+    st.bytes(&mut out);
+    assert_eq!(
+        out[..],
+        kat_hex("1f3f3a296d4e0a1e5259becacf5e060a347702902a30a527c3e7dc4683e5f016a1393b1d2bf76b189618055ef87330bc")[..]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_inverse_permute() {
+    let mut bytes = [0u8; 48];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = (i as u8).wrapping_mul(0x9d) ^ 0x5a;
+    }
+    let mut out = [0u8; 48];
+
+    let mut st = Xoodoo::from_bytes(bytes);
+    st.permute();
+    st.bytes(&mut out);
+    assert_ne!(out, bytes);
+    st.inverse_permute();
+    st.bytes(&mut out);
+    assert_eq!(out, bytes);
+
+    for rounds in 0..=XOODOO_MAX_ROUNDS {
+        let mut st = Xoodoo::from_bytes(bytes);
+        st.inverse_permute_rounds(rounds);
+        st.permute_rounds(rounds);
+        st.bytes(&mut out);
+        assert_eq!(out, bytes);
+
+        let mut st = Xoodoo::from_bytes(bytes);
+        st.permute_rounds(rounds);
+        st.inverse_permute_rounds(rounds);
+        st.bytes(&mut out);
+        assert_eq!(out, bytes);
+    }
+
+    let mut reduced = [0u8; 48];
+    reduced.copy_from_slice(&kat_hex("1f3f3a296d4e0a1e5259becacf5e060a347702902a30a527c3e7dc4683e5f016a1393b1d2bf76b189618055ef87330bc"));
+    let mut st = Xoodoo::from_bytes(reduced);
+    st.inverse_permute_rounds(6);
+    st.bytes(&mut out);
+    for (i, b) in out.iter().enumerate() {
+        assert_eq!(*b, i as u8);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_xoodoo_steps() {
+    let mut bytes = [0u8; 48];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    let mut out = [0u8; 48];
+    let mut expected = [0u8; 48];
+
+    let mut st = Xoodoo::from_bytes(bytes);
+    assert_eq!(st.lane(1, 0), 0x07060504);
+    assert_eq!(
+        st.plane(2),
+        [0x23222120, 0x27262524, 0x2b2a2928, 0x2f2e2d2c]
+    );
+    assert!(st.bit(0, 0, 8));
+    assert!(!st.bit(0, 0, 0));
+    st.set_bit(0, 0, 0, true);
+    assert_eq!(st.lane(0, 0), 0x03020101);
+    st.set_lane(0, 0, 0x03020100);
+    st.set_plane(1, [0x13121110, 0x17161514, 0x1b1a1918, 0x1f1e1d1c]);
+    st.bytes(&mut out);
+    assert_eq!(out, bytes);
+
+    let mut st = Xoodoo::from_bytes(bytes);
+    for round in 1 - XOODOO_MAX_ROUNDS as i32..=0 {
+        st.theta();
+        st.rho_west();
+        st.iota(Xoodoo::round_constant(round));
+        st.chi();
+        st.rho_east();
+    }
+    st.bytes(&mut out);
+    let mut st = Xoodoo::from_bytes(bytes);
+    st.permute();
+    st.bytes(&mut expected);
+    assert_eq!(out, expected);
+
+    let mut trace = vec![];
+    let mut st = Xoodoo::from_bytes(bytes);
+    st.permute_rounds_traced(6, |round, step, st| trace.push((round, step, st.clone())));
+    assert_eq!(trace.len(), 6 * 5);
+    assert_eq!((trace[7].0, trace[7].1), (1, XoodooStep::Iota));
+    st.bytes(&mut out);
+    let mut last = [0u8; 48];
+    trace[29].2.bytes(&mut last);
+    assert_eq!(out, last);
+    assert_eq!(
+        out[..],
+        kat_hex("1f3f3a296d4e0a1e5259becacf5e060a347702902a30a527c3e7dc4683e5f016a1393b1d2bf76b189618055ef87330bc")[..]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_xoofff() {
+    let key: Vec<u8> = (0..32).collect();
+    let msg: Vec<u8> = (0..=255).collect();
+
+    // Not XKCP output: computed with an independent model of Xoofff,
+    // still to be checked against the XKCP Xoofff test vectors
+    let mut xoofff = Xoofff::new(&key).unwrap();
+    xoofff.compress(b"");
+    assert_eq!(
+        xoofff.expand_to_vec(64, 0),
+        kat_hex("9262826af84756683ddc4ba91d9a2f076bd8f239b215ecbcaba59c0798707b35fcd5e7aa8c270011a39aa9bbf5e9b8b97cfe98030a0c761d162c7fab9b533603")
+    );
+
+    xoofff.restart();
+    xoofff.compress(&msg[..100]);
+    assert_eq!(
+        xoofff.expand_to_vec(100, 0),
+        kat_hex("ca06f83c2c3c52b651261c3e5e0062a1ad85a75decdaaad6f297b5b531b09cbd46b372ea99739b9c1a2d717289221eb3a8fe76efd059204ae0bff6a8ed3007c899a120895ace7754cd31837d9ca928f487e592117441e0178e60857d045b3508632a5bfd")
+    );
+
+    xoofff.restart();
+    xoofff.compress(&msg[..48]);
+    xoofff.compress(b"ab");
+    assert_eq!(
+        xoofff.expand_to_vec(48, 30),
+        kat_hex("af58f03bf7bddabb58bd545630d0a1501b757debe5501ca99c8df5fc6005eaea5a7ec87c037edae09e9688d5b7436785")
+    );
+
+    xoofff.restart();
+    assert_eq!(
+        xoofff.expand_to_vec(16, 0),
+        kat_hex("11dd69541775c2f9600d53bd6ae97f18")
+    );
+
+    xoofff.compress(&msg[..200]);
+    assert_eq!(
+        xoofff.expand_to_vec(32, 1000),
+        kat_hex("67104211310f0c97d3a72acea9bff0b7e69609dd7151abdae219d43201748e6e")
+    );
+    let all = xoofff.expand_to_vec(1032, 0);
+    for offset in [0, 1, 47, 48, 49, 100, 1000] {
+        assert_eq!(xoofff.expand_to_vec(1032 - offset, offset), all[offset..]);
+    }
+
+    assert!(Xoofff::new(&[0u8; XOOFFF_MAX_KEY_BYTES]).is_ok());
+    assert_eq!(
+        Xoofff::new(&[0u8; XOOFFF_MAX_KEY_BYTES + 1]).unwrap_err(),
+        XoodyakError::InvalidParameterLength
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_xoofff_sane_sanse() {
+    let key: Vec<u8> = (0..32).collect();
+    let msg: Vec<u8> = (0..=255).collect();
+    let messages: [(&[u8], &[u8]); 4] = [
+        (b"", &msg[..5]),
+        (b"ad", b""),
+        (&msg[..50], &msg[..100]),
+        (b"", b""),
+    ];
+
+    // Not XKCP output: computed with an independent model of Xoofff-SANE,
+    // still to be checked against the XKCP SANE test vectors
+    let sane_vectors = [
+        ("2c76b85cc1", "e2ecf0e546fb047c66efa142d5ebdd7c"),
+        ("", "1f03605b37a58ac586388c40b32fbc02"),
+        ("b0b97f78e3acfa00382718297c9d912246e1276ea9c353e254710adc8b8075ba5f44cf0a791863cb521d1df3b3586da30b7dd0ed51a0c0b1691fb6c5d7b76072a0b63d64a91979ad313982af1f5d60c1d2daa6f4c83ce581a063f720a366d978b56cadaf", "6be20eb27542e7fdb1198eec396f8928"),
+        ("", "505496e6d493a0ad655ffdfa1f1182dc"),
+    ];
+    let (mut sender, tag) = XoofffSane::new(&key, b"nonce").unwrap();
+    assert_eq!(
+        tag.as_ref(),
+        &kat_hex("dd97beb54f5be95b50e8b9a305cec845")[..]
+    );
+    let (mut receiver, tag2) = XoofffSane::new(&key, b"nonce").unwrap();
+    assert_eq!(tag, tag2);
+    for ((ad, m), (ct, t)) in messages.iter().zip(sane_vectors.iter()) {
+        let mut c = vec![0u8; m.len()];
+        let tag = sender.wrap(&mut c, Some(ad), m).unwrap();
+        assert_eq!(c, kat_hex(ct));
+        assert_eq!(tag.as_ref(), &kat_hex(t)[..]);
+        let mut p = vec![0u8; m.len()];
+        receiver.unwrap(&mut p, &tag, Some(ad), &c).unwrap();
+        assert_eq!(&p[..], *m);
+    }
+    assert_eq!(receiver.messages(), 4);
+    let mut c = msg[..10].to_vec();
+    let tag = sender.wrap_in_place(&mut c, None).unwrap();
+    c[0] ^= 1;
+    assert_eq!(
+        receiver.unwrap_in_place(&mut c, &tag, None).unwrap_err(),
+        XoodyakError::TagMismatch
+    );
+    assert_eq!(c, [0u8; 10]);
+    assert!(receiver.is_desynchronized());
+    assert_eq!(
+        receiver.unwrap_in_place(&mut c, &tag, None).unwrap_err(),
+        XoodyakError::Desynchronized
+    );
+
+    // Not XKCP output: computed with an independent model of Xoofff-SANSE,
+    // still to be checked against the XKCP SANSE test vectors
+    let sanse_vectors = [
+        ("7f01e5961d", "0f19f14e6d88a27a63cffb204f347959"),
+        ("", "ca90f705383e18224a7ade10ae1c7a15"),
+        ("80c0223c99fce0ad79179856899a469dd769fb7ecb03d0d020563245edf9d3565f957e1ad94ea3bb992bc43868efea2b7875f41ac740476f00e38d9e487f46baf110fe91962e0d5ce5fe89437e35ee675b07d768dad0a648c594792d8689603135d92bb1", "c036f5e7982133842d15eec77c81fc1d"),
+        ("", "fdb7f31b7fc7a644b4f0c5cfae7a6fc5"),
+    ];
+    let mut sender = XoofffSanse::new(&key).unwrap();
+    let mut receiver = XoofffSanse::new(&key).unwrap();
+    for ((ad, m), (ct, t)) in messages.iter().zip(sanse_vectors.iter()) {
+        let mut c = vec![0u8; m.len()];
+        let tag = sender.wrap(&mut c, Some(ad), m).unwrap();
+        assert_eq!(c, kat_hex(ct));
+        assert_eq!(tag.as_ref(), &kat_hex(t)[..]);
+        let mut p = vec![0u8; m.len()];
+        receiver.unwrap(&mut p, &tag, Some(ad), &c).unwrap();
+        assert_eq!(&p[..], *m);
+    }
+    let mut c = msg[..10].to_vec();
+    let tag = sender.wrap_in_place(&mut c, Some(b"ad")).unwrap();
+    assert_eq!(
+        receiver.unwrap_in_place(&mut c, &tag, None).unwrap_err(),
+        XoodyakError::TagMismatch
+    );
+    assert_eq!(c, [0u8; 10]);
+    assert!(receiver.is_desynchronized());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_xoofff_wbc() {
+    let key: Vec<u8> = (0..32).collect();
+    let msg: Vec<u8> = (0..=255).cycle().take(4096).collect();
+    let wbc = XoofffWbc::new(&key).unwrap();
+
+    // Not XKCP output: computed with an independent model of Xoofff-WBC,
+    // still to be checked against the XKCP WBC and WBC-AE test vectors
+    let vectors: [(&[u8], usize, &str, &str); 4] = [
+        (b"", 1, "f9", "f9"),
+        (b"tweak", 95, "7b506bbeda8648374a94450c7b1745f2719f8c38aba88040267f26787833eb4de95a74833e560af15cf7b863aee4c359", "5907f96a91ee2c1c1f7353f8dabe39f21046a3227475ac60c0982cdc685f82678ad44a6d30dcb8ea310147d3c05e354f"),
+        (&[1, 0, 0, 0, 0, 0, 0, 0], 512, "8796439d076ac10b9fd277a60edb6163738e1c509a86fd3f37199268ca63bdea93f1ec968350620b11df3dd29936b659", "8b540c35dc82b61f63a83f5824a0d2a4f8f45449bf7830b7daa46e10d54d9a523cf52911b46e0c847a593d5b1f91575d"),
+        (&[1, 0, 0, 0, 0, 0, 0, 0], 4096, "59609228bd910fcdfa327b01310fb50b2c180765d713f1c9c33bb475351a0962d17e6dc4bc1836522cf3565cade9cbc6", "f80542e57c55e66f577dbd00028648af832eb3a5c9faf53aad1b461d9b6e0a74b861b67513842abceb8b0b4f7d6ca0e4"),
+    ];
+    for (tweak, len, head, tail) in vectors.iter() {
+        let mut buf = msg[..*len].to_vec();
+        wbc.encrypt(tweak, &mut buf);
+        let n = head.len() / 2;
+        assert_eq!(buf[..n], kat_hex(head)[..]);
+        assert_eq!(buf[len - n..], kat_hex(tail)[..]);
+        wbc.decrypt(tweak, &mut buf);
+        assert_eq!(buf, msg[..*len]);
+    }
+
+    let mut sector = [0u8; 4096];
+    sector.copy_from_slice(&msg);
+    wbc.encrypt_sector(1, &mut sector);
+    let mut expected = msg.clone();
+    wbc.encrypt(&1u64.to_le_bytes(), &mut expected);
+    assert_eq!(sector[..], expected[..]);
+    let mut other = [0u8; 4096];
+    other.copy_from_slice(&msg);
+    other[4095] ^= 1;
+    wbc.encrypt_sector(1, &mut other);
+    assert_ne!(other[..16], sector[..16]);
+    wbc.decrypt_sector(1, &mut sector);
+    assert_eq!(sector[..], msg[..]);
+
+    for len in [0, 2, 93, 94, 96, 143, 144, 1000] {
+        let mut buf = msg[..len].to_vec();
+        wbc.encrypt(b"tweak", &mut buf);
+        wbc.decrypt(b"tweak", &mut buf);
+        assert_eq!(buf, msg[..len]);
+    }
+
+    let ae = XoofffWbcAe::new(&key).unwrap();
+    let ct = ae.encrypt_to_vec(Some(b"ad"), b"hello").unwrap();
+    assert_eq!(ct, kat_hex("43f98cb0f0a1a8f61f2394344d84c32c065a3836d9"));
+    assert_eq!(ae.decrypt_to_vec(Some(b"ad"), &ct).unwrap(), b"hello");
+    let mut in_out = ct.clone();
+    assert_eq!(
+        ae.decrypt_in_place(&mut in_out, None).unwrap_err(),
+        XoodyakError::TagMismatch
+    );
+    assert_eq!(in_out, vec![0u8; ct.len()]);
+    assert_eq!(
+        ae.decrypt_to_vec(None, &[0u8; 15]).unwrap_err(),
+        XoodyakError::InvalidBufferLength
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_xoofff_mac() {
+    let key: Vec<u8> = (0..32).collect();
+    let msg: Vec<u8> = (0..=255).cycle().take(2000).collect();
+
+    for len in [0, 1, 47, 48, 49, 191, 192, 193, 500, 2000] {
+        let mut xoofff = Xoofff::new(&key).unwrap();
+        xoofff.compress(&msg[..len]);
+        let mut expected = [0u8; XOODYAK_AUTH_TAG_BYTES];
+        xoofff.expand(&mut expected, 0);
+
+        let mut mac = XoofffMac::new(&key).unwrap();
+        mac.update(&msg[..len]);
+        assert_eq!(mac.finalize().as_ref(), &expected[..]);
+
+        for split in [1, 7, 48, 100, 192] {
+            let mut mac = XoofffMac::new(&key).unwrap();
+            for chunk in msg[..len].chunks(split) {
+                mac.update(chunk);
+            }
+            mac.clone().verify(&XoodyakTag::from(expected)).unwrap();
+            let mut long_tag = [0u8; 64];
+            mac.finalize_into(&mut long_tag);
+            assert_eq!(long_tag[..XOODYAK_AUTH_TAG_BYTES], expected);
+        }
+    }
+
+    let mut mac = XoofffMac::new(&key).unwrap();
+    mac.update(b"message");
+    let auth_tag = mac.clone().finalize();
+    mac.update(b"!");
+    assert_eq!(
+        mac.verify(&auth_tag).unwrap_err(),
+        XoodyakError::TagMismatch
+    );
+}
+
+#[test]
+fn test_xoodoo_backends() {
+    let mut bytes = [0u8; 48];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = (i as u8).wrapping_mul(0x3b) ^ 0xa5;
+    }
+    let mut expected = [0u8; 48];
+    let mut out = [0u8; 48];
+
+    for rounds in 0..=XOODOO_MAX_ROUNDS {
+        let mut st = Xoodoo::from_bytes(bytes);
+        st.permute_rounds_with(XoodooBackend::Portable, rounds)
+            .unwrap();
+        st.bytes(&mut expected);
+        for &backend in XoodooBackend::ALL.iter() {
+            let mut st = Xoodoo::from_bytes(bytes);
+            match st.permute_rounds_with(backend, rounds) {
+                Err(e) => {
+                    assert!(!backend.is_supported());
+                    assert_eq!(e, XoodyakError::UnsupportedBackend);
+                    continue;
+                }
+                Ok(()) => assert!(backend.is_supported()),
+            }
+            st.bytes(&mut out);
+            assert_eq!(out, expected, "{:?}", backend);
+            st.inverse_permute_rounds_with(backend, rounds).unwrap();
+            st.bytes(&mut out);
+            assert_eq!(out, bytes, "{:?}", backend);
+        }
+    }
+
+    let detected = XoodooBackend::detect();
+    assert!(detected.is_supported());
+    let mut st = Xoodoo::from_bytes(bytes);
+    st.permute_rounds_with(detected, XOODOO_MAX_ROUNDS).unwrap();
+    st.bytes(&mut expected);
+    let mut st = Xoodoo::from_bytes(bytes);
+    st.permute();
+    st.bytes(&mut out);
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn test_xoodoo_bytes() {
+    let mut bytes = [0u8; 48];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = (i as u8).wrapping_mul(0x47) ^ 0x3c;
+    }
+    let mut out = [0u8; 48];
+    Xoodoo::from_bytes(bytes).bytes(&mut out);
+    assert_eq!(out, bytes);
+
+    for len in 0..=48 {
+        let mut st = Xoodoo::from_bytes([0xffu8; 48]);
+        st.add_bytes(&bytes[..len]);
+        let mut expected = [0xffu8; 48];
+        for (e, b) in expected.iter_mut().zip(&bytes[..len]) {
+            *e ^= b;
+        }
+        st.bytes(&mut out);
+        assert_eq!(out, expected);
+
+        let mut partial = [0u8; 48];
+        st.extract_bytes(&mut partial[..len]);
+        assert_eq!(partial[..len], expected[..len]);
+        assert!(partial[len..].iter().all(|&b| b == 0));
+
+        st.add_byte(0x80, len % 48);
+        expected[len % 48] ^= 0x80;
+        st.bytes(&mut out);
+        assert_eq!(out, expected);
+    }
+
+    let st = Xoodoo::from_bytes(bytes);
+    for y in 0..3 {
+        for x in 0..4 {
+            let i = 4 * (4 * y + x);
+            let word = u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+            assert_eq!(st.lane(x, y), word);
+        }
+    }
+
+    let mut long = [0u8; 60];
+    long[..48].copy_from_slice(&bytes);
+    long[48..].fill(0xaa);
+    let mut st = Xoodoo::default();
+    st.add_bytes(&long);
+    st.bytes(&mut out);
+    assert_eq!(out, bytes);
+}
+
+#[cfg(feature = "std")]
+fn check_xoodoo_multi<const N: usize>() {
+    let mut states: Vec<Xoodoo> = vec![];
+    let mut multi = XoodooMulti::<N>::new();
+    for i in 0..N {
+        let mut bytes = [0u8; 48];
+        for (j, b) in bytes.iter_mut().enumerate() {
+            *b = (i * 48 + j) as u8 ^ 0x5c;
+        }
+        multi.add_bytes(i, &bytes[..47]);
+        multi.add_byte(i, bytes[47], 47);
+        states.push(Xoodoo::from_bytes(bytes));
+    }
+    let mut out = [0u8; 48];
+    let mut expected = [0u8; 48];
+    for rounds in [6, XOODOO_MAX_ROUNDS] {
+        let mut expected_states = states.clone();
+        for st in expected_states.iter_mut() {
+            st.permute_rounds(rounds);
+        }
+        for &backend in XoodooMultiBackend::ALL.iter() {
+            let mut permuted = multi.clone();
+            if permuted.permute_rounds_with(backend, rounds).is_err() {
+                assert!(!N.is_multiple_of(backend.lanes()) || !backend.is_supported());
+                continue;
+            }
+            for (i, st) in expected_states.iter().enumerate() {
+                st.bytes(&mut expected);
+                permuted.extract_bytes(i, &mut out);
+                assert_eq!(out, expected, "{:?} x{}", backend, N);
+                permuted.state(i).bytes(&mut out);
+                assert_eq!(out, expected);
+            }
+        }
+    }
+    let mut permuted = multi.clone();
+    permuted.permute();
+    let mut st = multi.state(N - 1);
+    st.permute();
+    st.bytes(&mut expected);
+    permuted.extract_bytes(N - 1, &mut out);
+    assert_eq!(out, expected);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_xoodoo_multi() {
+    check_xoodoo_multi::<1>();
+    check_xoodoo_multi::<4>();
+    check_xoodoo_multi::<8>();
+    check_xoodoo_multi::<16>();
+    check_xoodoo_multi::<32>();
+    assert_eq!(XoodooX16::STATES, 16);
+    assert!(XoodooX4::new()
+        .permute_rounds_with(XoodooMultiBackend::Avx2, 12)
+        .is_err());
+}
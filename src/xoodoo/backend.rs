@@ -0,0 +1,84 @@
+#[cfg(target_arch = "x86_64")]
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::error::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XoodooBackend {
+    Portable,
+    Sse2,
+    Ssse3,
+    Avx512Vl,
+}
+
+#[cfg(target_arch = "x86_64")]
+static BACKEND: AtomicU8 = AtomicU8::new(0);
+
+impl XoodooBackend {
+    pub const ALL: [XoodooBackend; 4] = [
+        XoodooBackend::Portable,
+        XoodooBackend::Sse2,
+        XoodooBackend::Ssse3,
+        XoodooBackend::Avx512Vl,
+    ];
+
+    pub fn is_supported(self) -> bool {
+        match self {
+            XoodooBackend::Portable => true,
+            #[cfg(target_arch = "x86_64")]
+            XoodooBackend::Sse2 => true,
+            #[cfg(all(target_arch = "x86_64", feature = "std"))]
+            XoodooBackend::Ssse3 => std::is_x86_feature_detected!("ssse3"),
+            #[cfg(all(target_arch = "x86_64", feature = "std"))]
+            XoodooBackend::Avx512Vl => {
+                std::is_x86_feature_detected!("avx512f")
+                    && std::is_x86_feature_detected!("avx512vl")
+            }
+            #[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+            XoodooBackend::Ssse3 => cfg!(target_feature = "ssse3"),
+            #[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+            XoodooBackend::Avx512Vl => {
+                cfg!(all(target_feature = "avx512f", target_feature = "avx512vl"))
+            }
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => false,
+        }
+    }
+
+    pub fn detect() -> Self {
+        XoodooBackend::ALL
+            .iter()
+            .rev()
+            .copied()
+            .find(|backend| backend.is_supported())
+            .unwrap_or(XoodooBackend::Portable)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[inline]
+    pub fn current() -> Self {
+        match BACKEND.load(Ordering::Relaxed) {
+            0 => {
+                let backend = Self::detect();
+                BACKEND.store(backend as u8 + 1, Ordering::Relaxed);
+                backend
+            }
+            id => XoodooBackend::ALL[id as usize - 1],
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    #[inline]
+    pub fn current() -> Self {
+        XoodooBackend::Portable
+    }
+
+    pub fn force(self) -> Result<(), Error> {
+        if !self.is_supported() {
+            return Err(Error::UnsupportedBackend);
+        }
+        #[cfg(target_arch = "x86_64")]
+        BACKEND.store(self as u8 + 1, Ordering::Relaxed);
+        Ok(())
+    }
+}
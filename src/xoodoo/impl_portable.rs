@@ -1,4 +1,4 @@
-use super::{Xoodoo, ROUND_KEYS};
+use super::{last_round_keys, Xoodoo};
 
 impl Xoodoo {
     #[inline(always)]
@@ -50,11 +50,77 @@ impl Xoodoo {
         st_words[11] = ((!tmp[1] & tmp[5]) ^ tmp[9]).rotate_left(8);
     }
 
-    pub fn permute(&mut self) {
-        let mut st_words = self.to_words();
-        for &round_key in &ROUND_KEYS {
-            Self::round(&mut st_words, round_key)
+    #[inline(always)]
+    fn inverse_round(st_words: &mut [u32; 12], round_key: u32) {
+        let mut tmp = [0u32; 12];
+
+        tmp[0] = st_words[0];
+        tmp[1] = st_words[1];
+        tmp[2] = st_words[2];
+        tmp[3] = st_words[3];
+
+        tmp[4] = st_words[4].rotate_right(1);
+        tmp[5] = st_words[5].rotate_right(1);
+        tmp[6] = st_words[6].rotate_right(1);
+        tmp[7] = st_words[7].rotate_right(1);
+
+        tmp[8] = st_words[10].rotate_right(8);
+        tmp[9] = st_words[11].rotate_right(8);
+        tmp[10] = st_words[8].rotate_right(8);
+        tmp[11] = st_words[9].rotate_right(8);
+
+        st_words[0] = (!tmp[4] & tmp[8]) ^ tmp[0] ^ round_key;
+        st_words[1] = (!tmp[5] & tmp[9]) ^ tmp[1];
+        st_words[2] = (!tmp[6] & tmp[10]) ^ tmp[2];
+        st_words[3] = (!tmp[7] & tmp[11]) ^ tmp[3];
+
+        st_words[4] = (!tmp[9] & tmp[1]) ^ tmp[5];
+        st_words[5] = (!tmp[10] & tmp[2]) ^ tmp[6];
+        st_words[6] = (!tmp[11] & tmp[3]) ^ tmp[7];
+        st_words[7] = (!tmp[8] & tmp[0]) ^ tmp[4];
+
+        st_words[8] = ((!tmp[0] & tmp[4]) ^ tmp[8]).rotate_right(11);
+        st_words[9] = ((!tmp[1] & tmp[5]) ^ tmp[9]).rotate_right(11);
+        st_words[10] = ((!tmp[2] & tmp[6]) ^ tmp[10]).rotate_right(11);
+        st_words[11] = ((!tmp[3] & tmp[7]) ^ tmp[11]).rotate_right(11);
+
+        // theta multiplies the column parity by 1 + e, whose inverse is the product of 1 + e^(2^k)
+        let mut p = [
+            st_words[0] ^ st_words[4] ^ st_words[8],
+            st_words[1] ^ st_words[5] ^ st_words[9],
+            st_words[2] ^ st_words[6] ^ st_words[10],
+            st_words[3] ^ st_words[7] ^ st_words[11],
+        ];
+        for k in 0..5 {
+            let (shift, r0, r1) = (1 << k, (5 << k) % 32, (14 << k) % 32);
+            let q = p;
+            for x in 0..4 {
+                let v = q[(x + 4 - shift % 4) % 4];
+                p[x] ^= v.rotate_left(r0) ^ v.rotate_left(r1);
+            }
+        }
+
+        let e = [
+            p[3].rotate_left(5) ^ p[3].rotate_left(14),
+            p[0].rotate_left(5) ^ p[0].rotate_left(14),
+            p[1].rotate_left(5) ^ p[1].rotate_left(14),
+            p[2].rotate_left(5) ^ p[2].rotate_left(14),
+        ];
+        for (i, st_word) in st_words.iter_mut().enumerate() {
+            *st_word ^= e[i % 4];
+        }
+    }
+
+    #[inline]
+    pub(super) fn permute_rounds_portable(&mut self, rounds: usize) {
+        for &round_key in last_round_keys(rounds) {
+            Self::round(&mut self.st, round_key)
+        }
+    }
+
+    pub(super) fn inverse_permute_rounds_portable(&mut self, rounds: usize) {
+        for &round_key in last_round_keys(rounds).iter().rev() {
+            Self::inverse_round(&mut self.st, round_key)
         }
-        self.init_from_words(st_words);
     }
 }
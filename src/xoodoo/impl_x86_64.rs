@@ -1,40 +1,209 @@
 use core::arch::x86_64::*;
 
-use super::{Xoodoo, ROUND_KEYS};
+use super::{last_round_keys, Xoodoo};
 
-impl Xoodoo {
-    #[allow(
-        non_upper_case_globals,
-        clippy::many_single_char_names,
-        clippy::cast_ptr_alignment
-    )]
-    pub fn permute(&mut self) {
-        let st = &mut self.st;
-        unsafe {
-            let rho_east_2 = _mm_set_epi32(0x0605_0407, 0x0201_0003, 0x0e0d_0c0f, 0x0a09_080b);
-            let mut a = _mm_loadu_si128(st.as_ptr().add(0 * 4) as *const _);
-            let mut b = _mm_loadu_si128(st.as_ptr().add(4 * 4) as *const _);
-            let mut c = _mm_loadu_si128(st.as_ptr().add(8 * 4) as *const _);
-            for &round_key in &ROUND_KEYS {
-                let mut p = _mm_shuffle_epi32(_mm_xor_si128(_mm_xor_si128(a, b), c), 0x93);
-                let mut e = _mm_or_si128(_mm_slli_epi32(p, 5), _mm_srli_epi32(p, 32 - 5));
-                p = _mm_or_si128(_mm_slli_epi32(p, 14), _mm_srli_epi32(p, 32 - 14));
-                e = _mm_xor_si128(e, p);
-                a = _mm_xor_si128(a, e);
-                b = _mm_xor_si128(b, e);
-                c = _mm_xor_si128(c, e);
-                b = _mm_shuffle_epi32(b, 0x93);
-                c = _mm_or_si128(_mm_slli_epi32(c, 11), _mm_srli_epi32(c, 32 - 11));
-                a = _mm_xor_si128(a, _mm_set_epi32(0, 0, 0, round_key as _));
-                a = _mm_xor_si128(a, _mm_andnot_si128(b, c));
-                b = _mm_xor_si128(b, _mm_andnot_si128(c, a));
-                c = _mm_xor_si128(c, _mm_andnot_si128(a, b));
-                b = _mm_or_si128(_mm_slli_epi32(b, 1), _mm_srli_epi32(b, 32 - 1));
-                c = _mm_shuffle_epi8(c, rho_east_2);
-            }
-            _mm_storeu_si128(st.as_mut_ptr().add(0 * 4) as *mut _, a);
-            _mm_storeu_si128(st.as_mut_ptr().add(4 * 4) as *mut _, b);
-            _mm_storeu_si128(st.as_mut_ptr().add(8 * 4) as *mut _, c);
+#[inline(always)]
+#[allow(
+    non_upper_case_globals,
+    clippy::many_single_char_names,
+    clippy::cast_ptr_alignment
+)]
+unsafe fn permute_rounds_sse<const SSSE3: bool>(st: &mut [u32; 12], rounds: usize) {
+    let rho_east_2 = _mm_set_epi32(0x0605_0407, 0x0201_0003, 0x0e0d_0c0f, 0x0a09_080b);
+    let mut a = _mm_loadu_si128(st.as_ptr().add(0) as *const _);
+    let mut b = _mm_loadu_si128(st.as_ptr().add(4) as *const _);
+    let mut c = _mm_loadu_si128(st.as_ptr().add(8) as *const _);
+    for &round_key in last_round_keys(rounds) {
+        let mut p = _mm_shuffle_epi32(_mm_xor_si128(_mm_xor_si128(a, b), c), 0x93);
+        let mut e = _mm_or_si128(_mm_slli_epi32(p, 5), _mm_srli_epi32(p, 32 - 5));
+        p = _mm_or_si128(_mm_slli_epi32(p, 14), _mm_srli_epi32(p, 32 - 14));
+        e = _mm_xor_si128(e, p);
+        a = _mm_xor_si128(a, e);
+        b = _mm_xor_si128(b, e);
+        c = _mm_xor_si128(c, e);
+        b = _mm_shuffle_epi32(b, 0x93);
+        c = _mm_or_si128(_mm_slli_epi32(c, 11), _mm_srli_epi32(c, 32 - 11));
+        a = _mm_xor_si128(a, _mm_set_epi32(0, 0, 0, round_key as _));
+        a = _mm_xor_si128(a, _mm_andnot_si128(b, c));
+        b = _mm_xor_si128(b, _mm_andnot_si128(c, a));
+        c = _mm_xor_si128(c, _mm_andnot_si128(a, b));
+        b = _mm_or_si128(_mm_slli_epi32(b, 1), _mm_srli_epi32(b, 32 - 1));
+        if SSSE3 {
+            c = _mm_shuffle_epi8(c, rho_east_2);
+        } else {
+            c = _mm_shuffle_epi32(c, 0x4e);
+            c = _mm_or_si128(_mm_slli_epi32(c, 8), _mm_srli_epi32(c, 32 - 8));
+        }
+    }
+    _mm_storeu_si128(st.as_mut_ptr().add(0) as *mut _, a);
+    _mm_storeu_si128(st.as_mut_ptr().add(4) as *mut _, b);
+    _mm_storeu_si128(st.as_mut_ptr().add(8) as *mut _, c);
+}
+
+#[inline(always)]
+#[allow(
+    non_upper_case_globals,
+    clippy::many_single_char_names,
+    clippy::cast_ptr_alignment
+)]
+unsafe fn inverse_permute_rounds_sse<const SSSE3: bool>(st: &mut [u32; 12], rounds: usize) {
+    let rho_east_2_inv = _mm_set_epi32(0x0407_0605, 0x0003_0201, 0x0c0f_0e0d, 0x080b_0a09);
+    let mut a = _mm_loadu_si128(st.as_ptr().add(0) as *const _);
+    let mut b = _mm_loadu_si128(st.as_ptr().add(4) as *const _);
+    let mut c = _mm_loadu_si128(st.as_ptr().add(8) as *const _);
+    for &round_key in last_round_keys(rounds).iter().rev() {
+        b = _mm_or_si128(_mm_srli_epi32(b, 1), _mm_slli_epi32(b, 32 - 1));
+        if SSSE3 {
+            c = _mm_shuffle_epi8(c, rho_east_2_inv);
+        } else {
+            c = _mm_shuffle_epi32(c, 0x4e);
+            c = _mm_or_si128(_mm_srli_epi32(c, 8), _mm_slli_epi32(c, 32 - 8));
         }
+        a = _mm_xor_si128(a, _mm_andnot_si128(b, c));
+        b = _mm_xor_si128(b, _mm_andnot_si128(c, a));
+        c = _mm_xor_si128(c, _mm_andnot_si128(a, b));
+        a = _mm_xor_si128(a, _mm_set_epi32(0, 0, 0, round_key as _));
+        b = _mm_shuffle_epi32(b, 0x39);
+        c = _mm_or_si128(_mm_srli_epi32(c, 11), _mm_slli_epi32(c, 32 - 11));
+
+        // theta multiplies the column parity by 1 + e, whose inverse is the product of 1 + e^(2^k)
+        let mut p = _mm_xor_si128(_mm_xor_si128(a, b), c);
+        let mut q = _mm_shuffle_epi32(p, 0x93);
+        q = _mm_xor_si128(
+            _mm_or_si128(_mm_slli_epi32(q, 5), _mm_srli_epi32(q, 32 - 5)),
+            _mm_or_si128(_mm_slli_epi32(q, 14), _mm_srli_epi32(q, 32 - 14)),
+        );
+        p = _mm_xor_si128(p, q);
+        q = _mm_shuffle_epi32(p, 0x4e);
+        q = _mm_xor_si128(
+            _mm_or_si128(_mm_slli_epi32(q, 10), _mm_srli_epi32(q, 32 - 10)),
+            _mm_or_si128(_mm_slli_epi32(q, 28), _mm_srli_epi32(q, 32 - 28)),
+        );
+        p = _mm_xor_si128(p, q);
+        q = _mm_xor_si128(
+            _mm_or_si128(_mm_slli_epi32(p, 20), _mm_srli_epi32(p, 32 - 20)),
+            _mm_or_si128(_mm_slli_epi32(p, 24), _mm_srli_epi32(p, 32 - 24)),
+        );
+        p = _mm_xor_si128(p, q);
+        q = _mm_xor_si128(
+            _mm_or_si128(_mm_slli_epi32(p, 8), _mm_srli_epi32(p, 32 - 8)),
+            _mm_or_si128(_mm_slli_epi32(p, 16), _mm_srli_epi32(p, 32 - 16)),
+        );
+        p = _mm_xor_si128(p, q);
+        q = _mm_xor_si128(
+            _mm_or_si128(_mm_slli_epi32(p, 16), _mm_srli_epi32(p, 32 - 16)),
+            p,
+        );
+        p = _mm_xor_si128(p, q);
+
+        p = _mm_shuffle_epi32(p, 0x93);
+        let mut e = _mm_or_si128(_mm_slli_epi32(p, 5), _mm_srli_epi32(p, 32 - 5));
+        p = _mm_or_si128(_mm_slli_epi32(p, 14), _mm_srli_epi32(p, 32 - 14));
+        e = _mm_xor_si128(e, p);
+        a = _mm_xor_si128(a, e);
+        b = _mm_xor_si128(b, e);
+        c = _mm_xor_si128(c, e);
+    }
+    _mm_storeu_si128(st.as_mut_ptr().add(0) as *mut _, a);
+    _mm_storeu_si128(st.as_mut_ptr().add(4) as *mut _, b);
+    _mm_storeu_si128(st.as_mut_ptr().add(8) as *mut _, c);
+}
+
+#[inline(always)]
+#[allow(
+    non_upper_case_globals,
+    clippy::many_single_char_names,
+    clippy::cast_ptr_alignment
+)]
+unsafe fn permute_rounds_avx512vl_inner(st: &mut [u32; 12], rounds: usize) {
+    let mut a = _mm_loadu_si128(st.as_ptr().add(0) as *const _);
+    let mut b = _mm_loadu_si128(st.as_ptr().add(4) as *const _);
+    let mut c = _mm_loadu_si128(st.as_ptr().add(8) as *const _);
+    for &round_key in last_round_keys(rounds) {
+        let p = _mm_shuffle_epi32(_mm_ternarylogic_epi32(a, b, c, 0x96), 0x93);
+        let e = _mm_xor_si128(_mm_rol_epi32(p, 5), _mm_rol_epi32(p, 14));
+        a = _mm_ternarylogic_epi32(a, e, _mm_set_epi32(0, 0, 0, round_key as _), 0x96);
+        b = _mm_shuffle_epi32(_mm_xor_si128(b, e), 0x93);
+        c = _mm_rol_epi32(_mm_xor_si128(c, e), 11);
+        a = _mm_ternarylogic_epi32(a, b, c, 0xd2);
+        b = _mm_ternarylogic_epi32(b, c, a, 0xd2);
+        c = _mm_ternarylogic_epi32(c, a, b, 0xd2);
+        b = _mm_rol_epi32(b, 1);
+        c = _mm_rol_epi32(_mm_shuffle_epi32(c, 0x4e), 8);
+    }
+    _mm_storeu_si128(st.as_mut_ptr().add(0) as *mut _, a);
+    _mm_storeu_si128(st.as_mut_ptr().add(4) as *mut _, b);
+    _mm_storeu_si128(st.as_mut_ptr().add(8) as *mut _, c);
+}
+
+#[inline(always)]
+#[allow(
+    non_upper_case_globals,
+    clippy::many_single_char_names,
+    clippy::cast_ptr_alignment
+)]
+unsafe fn inverse_permute_rounds_avx512vl_inner(st: &mut [u32; 12], rounds: usize) {
+    let mut a = _mm_loadu_si128(st.as_ptr().add(0) as *const _);
+    let mut b = _mm_loadu_si128(st.as_ptr().add(4) as *const _);
+    let mut c = _mm_loadu_si128(st.as_ptr().add(8) as *const _);
+    for &round_key in last_round_keys(rounds).iter().rev() {
+        b = _mm_ror_epi32(b, 1);
+        c = _mm_shuffle_epi32(_mm_ror_epi32(c, 8), 0x4e);
+        a = _mm_ternarylogic_epi32(a, b, c, 0xd2);
+        b = _mm_ternarylogic_epi32(b, c, a, 0xd2);
+        c = _mm_ternarylogic_epi32(c, a, b, 0xd2);
+        a = _mm_xor_si128(a, _mm_set_epi32(0, 0, 0, round_key as _));
+        b = _mm_shuffle_epi32(b, 0x39);
+        c = _mm_ror_epi32(c, 11);
+
+        let mut p = _mm_ternarylogic_epi32(a, b, c, 0x96);
+        let mut q = _mm_shuffle_epi32(p, 0x93);
+        p = _mm_ternarylogic_epi32(p, _mm_rol_epi32(q, 5), _mm_rol_epi32(q, 14), 0x96);
+        q = _mm_shuffle_epi32(p, 0x4e);
+        p = _mm_ternarylogic_epi32(p, _mm_rol_epi32(q, 10), _mm_rol_epi32(q, 28), 0x96);
+        p = _mm_ternarylogic_epi32(p, _mm_rol_epi32(p, 20), _mm_rol_epi32(p, 24), 0x96);
+        p = _mm_ternarylogic_epi32(p, _mm_rol_epi32(p, 8), _mm_rol_epi32(p, 16), 0x96);
+        p = _mm_rol_epi32(p, 16);
+
+        p = _mm_shuffle_epi32(p, 0x93);
+        let e = _mm_xor_si128(_mm_rol_epi32(p, 5), _mm_rol_epi32(p, 14));
+        a = _mm_xor_si128(a, e);
+        b = _mm_xor_si128(b, e);
+        c = _mm_xor_si128(c, e);
+    }
+    _mm_storeu_si128(st.as_mut_ptr().add(0) as *mut _, a);
+    _mm_storeu_si128(st.as_mut_ptr().add(4) as *mut _, b);
+    _mm_storeu_si128(st.as_mut_ptr().add(8) as *mut _, c);
+}
+
+impl Xoodoo {
+    #[inline]
+    pub(super) fn permute_rounds_sse2(&mut self, rounds: usize) {
+        unsafe { permute_rounds_sse::<false>(&mut self.st, rounds) }
+    }
+
+    #[inline]
+    pub(super) fn inverse_permute_rounds_sse2(&mut self, rounds: usize) {
+        unsafe { inverse_permute_rounds_sse::<false>(&mut self.st, rounds) }
+    }
+
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn permute_rounds_ssse3(&mut self, rounds: usize) {
+        permute_rounds_sse::<true>(&mut self.st, rounds)
+    }
+
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn inverse_permute_rounds_ssse3(&mut self, rounds: usize) {
+        inverse_permute_rounds_sse::<true>(&mut self.st, rounds)
+    }
+
+    #[target_feature(enable = "avx512f,avx512vl")]
+    pub(super) unsafe fn permute_rounds_avx512vl(&mut self, rounds: usize) {
+        permute_rounds_avx512vl_inner(&mut self.st, rounds)
+    }
+
+    #[target_feature(enable = "avx512f,avx512vl")]
+    pub(super) unsafe fn inverse_permute_rounds_avx512vl(&mut self, rounds: usize) {
+        inverse_permute_rounds_avx512vl_inner(&mut self.st, rounds)
     }
 }
@@ -1,109 +1,277 @@
 use core::convert::TryInto;
 use zeroize::Zeroize;
 
-#[cfg(not(target_arch = "x86_64"))]
+use crate::error::Error;
+use crate::permutation::Permutation;
+
+mod backend;
 mod impl_portable;
 #[cfg(target_arch = "x86_64")]
 mod impl_x86_64;
+mod multi;
+#[cfg(target_arch = "x86_64")]
+mod multi_x86_64;
+mod roll;
+mod steps;
 
-const ROUND_KEYS: [u32; 12] = [
-    0x058, 0x038, 0x3c0, 0x0d0, 0x120, 0x014, 0x060, 0x02c, 0x380, 0x0f0, 0x1a0, 0x012,
-];
+pub use self::backend::XoodooBackend;
+pub use self::multi::{XoodooMulti, XoodooMultiBackend, XoodooX16, XoodooX4, XoodooX8};
+pub use self::steps::XoodooStep;
 
-#[derive(Clone, Debug)]
-pub struct Xoodoo {
-    st: [u8; 48],
+pub const XOODOO_MAX_ROUNDS: usize = 12;
+
+// c_i = (x^3 + s_i) * x^p_i, with s_i = x^i mod (1 + x + x^3) and p_i = 3^i mod 7
+const fn round_constant(i: i32) -> u32 {
+    let mut s = 1u32;
+    let mut j = 0;
+    while j < i.rem_euclid(7) {
+        s <<= 1;
+        if s & 0x8 != 0 {
+            s ^= 0xb;
+        }
+        j += 1;
+    }
+    let mut p = 1u32;
+    let mut j = 0;
+    while j < i.rem_euclid(6) {
+        p = (p * 3) % 7;
+        j += 1;
+    }
+    (s | 0x8) << p
 }
 
-impl Default for Xoodoo {
-    fn default() -> Self {
-        Self { st: [0u8; 48] }
+const fn round_keys() -> [u32; XOODOO_MAX_ROUNDS] {
+    let mut keys = [0u32; XOODOO_MAX_ROUNDS];
+    let mut k = 0;
+    while k < XOODOO_MAX_ROUNDS {
+        keys[k] = round_constant(k as i32 + 1 - XOODOO_MAX_ROUNDS as i32);
+        k += 1;
     }
+    keys
+}
+
+pub(crate) const ROUND_KEYS: [u32; XOODOO_MAX_ROUNDS] = round_keys();
+
+#[inline(always)]
+fn last_round_keys(rounds: usize) -> &'static [u32] {
+    assert!(
+        rounds <= XOODOO_MAX_ROUNDS,
+        "Xoodoo supports at most 12 rounds"
+    );
+    &ROUND_KEYS[XOODOO_MAX_ROUNDS - rounds..]
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Xoodoo {
+    st: [u32; 12],
 }
 
 impl Xoodoo {
     #[inline(always)]
-    fn bytes_view(&self) -> &[u8] {
-        &self.st
+    fn to_words(&self) -> [u32; 12] {
+        self.st
     }
 
     #[inline(always)]
-    fn bytes_view_mut(&mut self) -> &mut [u8] {
-        &mut self.st
+    fn init_from_words(&mut self, st_words: [u32; 12]) {
+        self.st = st_words;
     }
 
-    #[inline(always)]
-    #[allow(dead_code)]
-    fn to_words(&self) -> [u32; 12] {
-        let mut st_words = [0u32; 12];
-        for (st_word, bytes) in st_words.iter_mut().zip(self.st.chunks_exact(4)) {
-            *st_word = u32::from_le_bytes(bytes.try_into().unwrap());
+    #[inline]
+    pub fn permute(&mut self) {
+        self.permute_rounds(XOODOO_MAX_ROUNDS)
+    }
+
+    #[inline]
+    pub fn permute_rounds(&mut self, rounds: usize) {
+        self.permute_rounds_unchecked(XoodooBackend::current(), rounds)
+    }
+
+    pub fn permute_rounds_with(
+        &mut self,
+        backend: XoodooBackend,
+        rounds: usize,
+    ) -> Result<(), Error> {
+        if !backend.is_supported() {
+            return Err(Error::UnsupportedBackend);
         }
-        st_words
+        self.permute_rounds_unchecked(backend, rounds);
+        Ok(())
     }
 
     #[inline(always)]
-    #[allow(dead_code)]
-    fn init_from_words(&mut self, st_words: [u32; 12]) {
-        for (bytes, st_word) in self.st.chunks_exact_mut(4).zip(st_words.iter()) {
-            bytes.copy_from_slice(&st_word.to_le_bytes());
+    fn permute_rounds_unchecked(&mut self, backend: XoodooBackend, rounds: usize) {
+        match backend {
+            #[cfg(target_arch = "x86_64")]
+            XoodooBackend::Avx512Vl => unsafe { self.permute_rounds_avx512vl(rounds) },
+            #[cfg(target_arch = "x86_64")]
+            XoodooBackend::Ssse3 => unsafe { self.permute_rounds_ssse3(rounds) },
+            #[cfg(target_arch = "x86_64")]
+            XoodooBackend::Sse2 => self.permute_rounds_sse2(rounds),
+            _ => self.permute_rounds_portable(rounds),
         }
     }
 
-    #[cfg(not(target_endian = "little"))]
-    #[inline(always)]
-    fn endian_swap(&mut self) {
-        let mut st_words = self.to_words();
-        for st_word in &mut st_words {
-            *st_word = (*st_word).to_le()
+    #[inline]
+    pub fn inverse_permute(&mut self) {
+        self.inverse_permute_rounds(XOODOO_MAX_ROUNDS)
+    }
+
+    #[inline]
+    pub fn inverse_permute_rounds(&mut self, rounds: usize) {
+        self.inverse_permute_rounds_unchecked(XoodooBackend::current(), rounds)
+    }
+
+    pub fn inverse_permute_rounds_with(
+        &mut self,
+        backend: XoodooBackend,
+        rounds: usize,
+    ) -> Result<(), Error> {
+        if !backend.is_supported() {
+            return Err(Error::UnsupportedBackend);
         }
-        self.init_from_words(st_words);
+        self.inverse_permute_rounds_unchecked(backend, rounds);
+        Ok(())
     }
 
-    #[cfg(target_endian = "little")]
     #[inline(always)]
-    fn endian_swap(&mut self) {
-        _ = self
+    fn inverse_permute_rounds_unchecked(&mut self, backend: XoodooBackend, rounds: usize) {
+        match backend {
+            #[cfg(target_arch = "x86_64")]
+            XoodooBackend::Avx512Vl => unsafe { self.inverse_permute_rounds_avx512vl(rounds) },
+            #[cfg(target_arch = "x86_64")]
+            XoodooBackend::Ssse3 => unsafe { self.inverse_permute_rounds_ssse3(rounds) },
+            #[cfg(target_arch = "x86_64")]
+            XoodooBackend::Sse2 => self.inverse_permute_rounds_sse2(rounds),
+            _ => self.inverse_permute_rounds_portable(rounds),
+        }
     }
 
     #[inline]
     pub fn from_bytes(bytes: [u8; 48]) -> Self {
         let mut st = Xoodoo::default();
-        let st_bytes = st.bytes_view_mut();
-        st_bytes.copy_from_slice(&bytes);
+        for (st_word, chunk) in st.st.iter_mut().zip(bytes.chunks_exact(4)) {
+            *st_word = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
         st
     }
 
     #[inline(always)]
     pub fn bytes(&self, out: &mut [u8; 48]) {
-        let st_bytes = self.bytes_view();
-        out.copy_from_slice(st_bytes);
+        for (chunk, st_word) in out.chunks_exact_mut(4).zip(self.st.iter()) {
+            chunk.copy_from_slice(&st_word.to_le_bytes());
+        }
     }
 
     #[inline(always)]
     pub fn add_byte(&mut self, byte: u8, offset: usize) {
-        self.endian_swap();
-        let st_bytes = self.bytes_view_mut();
-        st_bytes[offset] ^= byte;
-        self.endian_swap();
+        self.st[offset / 4] ^= (byte as u32) << (8 * (offset % 4));
     }
 
     #[inline(always)]
     pub fn add_bytes(&mut self, bytes: &[u8]) {
-        self.endian_swap();
-        let st_bytes = self.bytes_view_mut();
-        for (st_byte, byte) in st_bytes.iter_mut().zip(bytes) {
-            *st_byte ^= byte;
+        let bytes = &bytes[..bytes.len().min(48)];
+        let mut chunks = bytes.chunks_exact(4);
+        for (st_word, chunk) in self.st.iter_mut().zip(&mut chunks) {
+            *st_word ^= u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+        let offset = bytes.len() - chunks.remainder().len();
+        for (i, &byte) in chunks.remainder().iter().enumerate() {
+            self.add_byte(byte, offset + i);
         }
-        self.endian_swap();
     }
 
     #[inline(always)]
     pub fn extract_bytes(&mut self, out: &mut [u8]) {
-        self.endian_swap();
-        let st_bytes = self.bytes_view();
-        out.copy_from_slice(&st_bytes[..out.len()]);
-        self.endian_swap();
+        assert!(out.len() <= 48);
+        let full_words = out.len() / 4;
+        let mut chunks = out.chunks_exact_mut(4);
+        for (chunk, st_word) in (&mut chunks).zip(self.st.iter()) {
+            chunk.copy_from_slice(&st_word.to_le_bytes());
+        }
+        let rem = chunks.into_remainder();
+        if !rem.is_empty() {
+            let len = rem.len();
+            rem.copy_from_slice(&self.st[full_words].to_le_bytes()[..len]);
+        }
+    }
+
+    #[inline(always)]
+    fn crypt_bytes(&mut self, out: &mut [u8], bin: &[u8], decrypt: bool) {
+        assert!(bin.len() <= 48 && out.len() >= bin.len());
+        let out = &mut out[..bin.len()];
+        if bin.len() == <Self as Permutation>::KEYED_SQUEEZE_RATE {
+            for (i, (st_word, out_chunk)) in self.st[..6]
+                .iter_mut()
+                .zip(out.chunks_exact_mut(4))
+                .enumerate()
+            {
+                let word = u32::from_le_bytes(bin[4 * i..4 * i + 4].try_into().unwrap());
+                out_chunk.copy_from_slice(&(*st_word ^ word).to_le_bytes());
+                *st_word = if decrypt { word } else { *st_word ^ word };
+            }
+            return;
+        }
+        let mut out_chunks = out.chunks_exact_mut(4);
+        let mut chunks = bin.chunks_exact(4);
+        for ((st_word, out_chunk), chunk) in
+            self.st.iter_mut().zip(&mut out_chunks).zip(&mut chunks)
+        {
+            let word = u32::from_le_bytes(chunk.try_into().unwrap());
+            out_chunk.copy_from_slice(&(*st_word ^ word).to_le_bytes());
+            *st_word = if decrypt { word } else { *st_word ^ word };
+        }
+        let offset = bin.len() - chunks.remainder().len();
+        for ((i, out_byte), &byte) in out_chunks
+            .into_remainder()
+            .iter_mut()
+            .enumerate()
+            .zip(chunks.remainder())
+        {
+            let shift = 8 * ((offset + i) % 4);
+            let st_word = &mut self.st[(offset + i) / 4];
+            *out_byte = (*st_word >> shift) as u8 ^ byte;
+            *st_word ^= (if decrypt { *out_byte } else { byte } as u32) << shift;
+        }
+    }
+}
+
+impl Permutation for Xoodoo {
+    const STATE_BYTES: usize = 48;
+    const HASH_ABSORB_RATE: usize = 16;
+    const HASH_SQUEEZE_RATE: usize = 16;
+    const KEYED_ABSORB_RATE: usize = 44;
+    const KEYED_SQUEEZE_RATE: usize = 24;
+    const RATCHET_RATE: usize = 16;
+
+    #[inline(always)]
+    fn permute(&mut self) {
+        Xoodoo::permute(self)
+    }
+
+    #[inline(always)]
+    fn add_byte(&mut self, byte: u8, offset: usize) {
+        Xoodoo::add_byte(self, byte, offset)
+    }
+
+    #[inline(always)]
+    fn add_bytes(&mut self, bytes: &[u8]) {
+        Xoodoo::add_bytes(self, bytes)
+    }
+
+    #[inline(always)]
+    fn extract_bytes(&mut self, out: &mut [u8]) {
+        Xoodoo::extract_bytes(self, out)
+    }
+
+    #[inline(always)]
+    fn encrypt_bytes(&mut self, out: &mut [u8], bin: &[u8]) {
+        self.crypt_bytes(out, bin, false)
+    }
+
+    #[inline(always)]
+    fn decrypt_bytes(&mut self, out: &mut [u8], bin: &[u8]) {
+        self.crypt_bytes(out, bin, true)
     }
 }
 
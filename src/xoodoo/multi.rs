@@ -0,0 +1,293 @@
+use zeroize::Zeroize;
+
+use super::{last_round_keys, Xoodoo, XOODOO_MAX_ROUNDS};
+use crate::error::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XoodooMultiBackend {
+    Portable,
+    Sse2,
+    Avx2,
+    Avx512,
+}
+
+impl XoodooMultiBackend {
+    pub const ALL: [XoodooMultiBackend; 4] = [
+        XoodooMultiBackend::Portable,
+        XoodooMultiBackend::Sse2,
+        XoodooMultiBackend::Avx2,
+        XoodooMultiBackend::Avx512,
+    ];
+
+    pub fn lanes(self) -> usize {
+        match self {
+            XoodooMultiBackend::Portable => 1,
+            XoodooMultiBackend::Sse2 => 4,
+            XoodooMultiBackend::Avx2 => 8,
+            XoodooMultiBackend::Avx512 => 16,
+        }
+    }
+
+    pub fn is_supported(self) -> bool {
+        match self {
+            XoodooMultiBackend::Portable => true,
+            #[cfg(target_arch = "x86_64")]
+            XoodooMultiBackend::Sse2 => true,
+            #[cfg(all(target_arch = "x86_64", feature = "std"))]
+            XoodooMultiBackend::Avx2 => std::is_x86_feature_detected!("avx2"),
+            #[cfg(all(target_arch = "x86_64", feature = "std"))]
+            XoodooMultiBackend::Avx512 => std::is_x86_feature_detected!("avx512f"),
+            #[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+            XoodooMultiBackend::Avx2 => cfg!(target_feature = "avx2"),
+            #[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+            XoodooMultiBackend::Avx512 => cfg!(target_feature = "avx512f"),
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => false,
+        }
+    }
+
+    pub fn detect(states: usize) -> Self {
+        XoodooMultiBackend::ALL
+            .iter()
+            .rev()
+            .copied()
+            .find(|backend| states.is_multiple_of(backend.lanes()) && backend.is_supported())
+            .unwrap_or(XoodooMultiBackend::Portable)
+    }
+}
+
+pub(super) trait Lanes: Copy {
+    const LANES: usize;
+
+    unsafe fn load(p: *const u32) -> Self;
+    unsafe fn store(self, p: *mut u32);
+    unsafe fn splat(x: u32) -> Self;
+    unsafe fn xor(self, other: Self) -> Self;
+    unsafe fn andnot(self, other: Self) -> Self;
+    unsafe fn rotl<const L: i32, const R: i32>(self) -> Self;
+}
+
+impl Lanes for u32 {
+    const LANES: usize = 1;
+
+    #[inline(always)]
+    unsafe fn load(p: *const u32) -> Self {
+        *p
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, p: *mut u32) {
+        *p = self
+    }
+
+    #[inline(always)]
+    unsafe fn splat(x: u32) -> Self {
+        x
+    }
+
+    #[inline(always)]
+    unsafe fn xor(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    #[inline(always)]
+    unsafe fn andnot(self, other: Self) -> Self {
+        !self & other
+    }
+
+    #[inline(always)]
+    unsafe fn rotl<const L: i32, const R: i32>(self) -> Self {
+        self.rotate_left(L as u32)
+    }
+}
+
+#[inline(always)]
+unsafe fn round<V: Lanes>(a: &mut [V; 12], round_key: V) {
+    let p = [
+        a[0].xor(a[4]).xor(a[8]),
+        a[1].xor(a[5]).xor(a[9]),
+        a[2].xor(a[6]).xor(a[10]),
+        a[3].xor(a[7]).xor(a[11]),
+    ];
+    let e = [
+        p[3].rotl::<5, 27>().xor(p[3].rotl::<14, 18>()),
+        p[0].rotl::<5, 27>().xor(p[0].rotl::<14, 18>()),
+        p[1].rotl::<5, 27>().xor(p[1].rotl::<14, 18>()),
+        p[2].rotl::<5, 27>().xor(p[2].rotl::<14, 18>()),
+    ];
+
+    let b0 = [
+        a[0].xor(e[0]).xor(round_key),
+        a[1].xor(e[1]),
+        a[2].xor(e[2]),
+        a[3].xor(e[3]),
+    ];
+    let b1 = [
+        a[7].xor(e[3]),
+        a[4].xor(e[0]),
+        a[5].xor(e[1]),
+        a[6].xor(e[2]),
+    ];
+    let b2 = [
+        a[8].xor(e[0]).rotl::<11, 21>(),
+        a[9].xor(e[1]).rotl::<11, 21>(),
+        a[10].xor(e[2]).rotl::<11, 21>(),
+        a[11].xor(e[3]).rotl::<11, 21>(),
+    ];
+
+    for x in 0..4 {
+        a[x] = b0[x].xor(b1[x].andnot(b2[x]));
+        a[4 + x] = b1[x].xor(b2[x].andnot(b0[x])).rotl::<1, 31>();
+        a[8 + (x + 2) % 4] = b2[x].xor(b0[x].andnot(b1[x])).rotl::<8, 24>();
+    }
+}
+
+#[inline(always)]
+pub(super) unsafe fn permute_lanes<V: Lanes, const N: usize>(
+    words: &mut [[u32; N]; 12],
+    rounds: usize,
+) {
+    debug_assert_eq!(N % V::LANES, 0);
+    for offset in (0..N).step_by(V::LANES) {
+        let mut a = [V::splat(0); 12];
+        for (a, word) in a.iter_mut().zip(words.iter()) {
+            *a = V::load(word.as_ptr().add(offset));
+        }
+        for &round_key in last_round_keys(rounds) {
+            round(&mut a, V::splat(round_key));
+        }
+        for (a, word) in a.iter().zip(words.iter_mut()) {
+            a.store(word.as_mut_ptr().add(offset));
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct XoodooMulti<const N: usize> {
+    words: [[u32; N]; 12],
+}
+
+pub type XoodooX4 = XoodooMulti<4>;
+pub type XoodooX8 = XoodooMulti<8>;
+pub type XoodooX16 = XoodooMulti<16>;
+
+impl<const N: usize> Default for XoodooMulti<N> {
+    fn default() -> Self {
+        XoodooMulti {
+            words: [[0u32; N]; 12],
+        }
+    }
+}
+
+impl<const N: usize> XoodooMulti<N> {
+    pub const STATES: usize = N;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_states(states: &[Xoodoo; N]) -> Self {
+        let mut multi = Self::default();
+        for (i, st) in states.iter().enumerate() {
+            multi.set_state(i, st);
+        }
+        multi
+    }
+
+    pub fn state(&self, i: usize) -> Xoodoo {
+        let mut st_words = [0u32; 12];
+        for (st_word, word) in st_words.iter_mut().zip(self.words.iter()) {
+            *st_word = word[i];
+        }
+        let mut st = Xoodoo::default();
+        st.init_from_words(st_words);
+        st
+    }
+
+    pub fn set_state(&mut self, i: usize, st: &Xoodoo) {
+        for (word, st_word) in self.words.iter_mut().zip(st.to_words().iter()) {
+            word[i] = *st_word;
+        }
+    }
+
+    #[inline]
+    pub fn add_byte(&mut self, i: usize, byte: u8, offset: usize) {
+        self.words[offset / 4][i] ^= (byte as u32) << (8 * (offset % 4));
+    }
+
+    pub fn add_bytes(&mut self, i: usize, bytes: &[u8]) {
+        assert!(bytes.len() <= 48);
+        let mut chunks = bytes.chunks_exact(4);
+        for (word, chunk) in self.words.iter_mut().zip(&mut chunks) {
+            word[i] ^= u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        let offset = bytes.len() - chunks.remainder().len();
+        for (j, &byte) in chunks.remainder().iter().enumerate() {
+            self.add_byte(i, byte, offset + j);
+        }
+    }
+
+    pub fn extract_bytes(&self, i: usize, out: &mut [u8]) {
+        assert!(out.len() <= 48);
+        for (j, byte) in out.iter_mut().enumerate() {
+            *byte = (self.words[j / 4][i] >> (8 * (j % 4))) as u8;
+        }
+    }
+
+    pub(crate) fn accumulate_into(&self, st: &mut Xoodoo) {
+        let mut st_words = st.to_words();
+        for (st_word, word) in st_words.iter_mut().zip(self.words.iter()) {
+            *st_word ^= word.iter().fold(0, |acc, x| acc ^ x);
+        }
+        st.init_from_words(st_words);
+    }
+
+    #[inline]
+    pub fn permute(&mut self) {
+        self.permute_rounds(XOODOO_MAX_ROUNDS)
+    }
+
+    #[inline]
+    pub fn permute_rounds(&mut self, rounds: usize) {
+        self.permute_rounds_unchecked(XoodooMultiBackend::detect(N), rounds)
+    }
+
+    pub fn permute_rounds_with(
+        &mut self,
+        backend: XoodooMultiBackend,
+        rounds: usize,
+    ) -> Result<(), Error> {
+        if !N.is_multiple_of(backend.lanes()) || !backend.is_supported() {
+            return Err(Error::UnsupportedBackend);
+        }
+        self.permute_rounds_unchecked(backend, rounds);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn permute_rounds_unchecked(&mut self, backend: XoodooMultiBackend, rounds: usize) {
+        match backend {
+            #[cfg(target_arch = "x86_64")]
+            XoodooMultiBackend::Avx512 => unsafe {
+                super::multi_x86_64::permute_avx512(&mut self.words, rounds)
+            },
+            #[cfg(target_arch = "x86_64")]
+            XoodooMultiBackend::Avx2 => unsafe {
+                super::multi_x86_64::permute_avx2(&mut self.words, rounds)
+            },
+            #[cfg(target_arch = "x86_64")]
+            XoodooMultiBackend::Sse2 => unsafe {
+                super::multi_x86_64::permute_sse2(&mut self.words, rounds)
+            },
+            _ => unsafe { permute_lanes::<u32, N>(&mut self.words, rounds) },
+        }
+    }
+}
+
+impl<const N: usize> Drop for XoodooMulti<N> {
+    fn drop(&mut self) {
+        for word in self.words.iter_mut() {
+            word.zeroize();
+        }
+    }
+}
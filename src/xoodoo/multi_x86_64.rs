@@ -0,0 +1,119 @@
+use core::arch::x86_64::*;
+
+use super::multi::{permute_lanes, Lanes};
+
+impl Lanes for __m128i {
+    const LANES: usize = 4;
+
+    #[inline(always)]
+    unsafe fn load(p: *const u32) -> Self {
+        _mm_loadu_si128(p as *const _)
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, p: *mut u32) {
+        _mm_storeu_si128(p as *mut _, self)
+    }
+
+    #[inline(always)]
+    unsafe fn splat(x: u32) -> Self {
+        _mm_set1_epi32(x as _)
+    }
+
+    #[inline(always)]
+    unsafe fn xor(self, other: Self) -> Self {
+        _mm_xor_si128(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn andnot(self, other: Self) -> Self {
+        _mm_andnot_si128(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn rotl<const L: i32, const R: i32>(self) -> Self {
+        _mm_or_si128(_mm_slli_epi32::<L>(self), _mm_srli_epi32::<R>(self))
+    }
+}
+
+impl Lanes for __m256i {
+    const LANES: usize = 8;
+
+    #[inline(always)]
+    unsafe fn load(p: *const u32) -> Self {
+        _mm256_loadu_si256(p as *const _)
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, p: *mut u32) {
+        _mm256_storeu_si256(p as *mut _, self)
+    }
+
+    #[inline(always)]
+    unsafe fn splat(x: u32) -> Self {
+        _mm256_set1_epi32(x as _)
+    }
+
+    #[inline(always)]
+    unsafe fn xor(self, other: Self) -> Self {
+        _mm256_xor_si256(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn andnot(self, other: Self) -> Self {
+        _mm256_andnot_si256(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn rotl<const L: i32, const R: i32>(self) -> Self {
+        _mm256_or_si256(_mm256_slli_epi32::<L>(self), _mm256_srli_epi32::<R>(self))
+    }
+}
+
+impl Lanes for __m512i {
+    const LANES: usize = 16;
+
+    #[inline(always)]
+    unsafe fn load(p: *const u32) -> Self {
+        _mm512_loadu_si512(p as *const _)
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, p: *mut u32) {
+        _mm512_storeu_si512(p as *mut _, self)
+    }
+
+    #[inline(always)]
+    unsafe fn splat(x: u32) -> Self {
+        _mm512_set1_epi32(x as _)
+    }
+
+    #[inline(always)]
+    unsafe fn xor(self, other: Self) -> Self {
+        _mm512_xor_si512(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn andnot(self, other: Self) -> Self {
+        _mm512_andnot_si512(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn rotl<const L: i32, const R: i32>(self) -> Self {
+        _mm512_rol_epi32::<L>(self)
+    }
+}
+
+pub(super) unsafe fn permute_sse2<const N: usize>(words: &mut [[u32; N]; 12], rounds: usize) {
+    permute_lanes::<__m128i, N>(words, rounds)
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn permute_avx2<const N: usize>(words: &mut [[u32; N]; 12], rounds: usize) {
+    permute_lanes::<__m256i, N>(words, rounds)
+}
+
+#[target_feature(enable = "avx512f")]
+pub(super) unsafe fn permute_avx512<const N: usize>(words: &mut [[u32; N]; 12], rounds: usize) {
+    permute_lanes::<__m512i, N>(words, rounds)
+}
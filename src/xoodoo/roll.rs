@@ -0,0 +1,30 @@
+use super::Xoodoo;
+
+impl Xoodoo {
+    #[inline]
+    pub(crate) fn add_state(&mut self, other: &Xoodoo) {
+        for (st_word, other_word) in self.st.iter_mut().zip(other.st.iter()) {
+            *st_word ^= other_word;
+        }
+    }
+
+    #[inline(always)]
+    fn roll_planes(&mut self, f: impl FnOnce(&[u32; 12]) -> u32) {
+        let rolled = f(&self.st);
+        self.st.rotate_left(4);
+        self.st[8..].rotate_left(1);
+        self.st[11] = rolled;
+    }
+
+    #[inline]
+    pub(crate) fn roll_xc(&mut self) {
+        self.roll_planes(|a| a[0] ^ (a[0] << 13) ^ a[4].rotate_left(3));
+    }
+
+    #[inline]
+    pub(crate) fn roll_xe(&mut self) {
+        self.roll_planes(|a| {
+            (a[4] & a[8]) ^ a[0].rotate_left(5) ^ a[4].rotate_left(13) ^ 0x0000_0007
+        });
+    }
+}
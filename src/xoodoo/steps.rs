@@ -0,0 +1,147 @@
+use super::{last_round_keys, round_constant, Xoodoo, XOODOO_MAX_ROUNDS};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XoodooStep {
+    Theta,
+    RhoWest,
+    Iota,
+    Chi,
+    RhoEast,
+}
+
+impl XoodooStep {
+    pub const ROUND: [XoodooStep; 5] = [
+        XoodooStep::Theta,
+        XoodooStep::RhoWest,
+        XoodooStep::Iota,
+        XoodooStep::Chi,
+        XoodooStep::RhoEast,
+    ];
+}
+
+impl Xoodoo {
+    pub const fn round_constant(index: i32) -> u32 {
+        round_constant(index)
+    }
+
+    #[inline]
+    pub fn lane(&self, x: usize, y: usize) -> u32 {
+        assert!(x < 4 && y < 3);
+        self.to_words()[4 * y + x]
+    }
+
+    #[inline]
+    pub fn set_lane(&mut self, x: usize, y: usize, lane: u32) {
+        assert!(x < 4 && y < 3);
+        let mut st_words = self.to_words();
+        st_words[4 * y + x] = lane;
+        self.init_from_words(st_words);
+    }
+
+    #[inline]
+    pub fn plane(&self, y: usize) -> [u32; 4] {
+        assert!(y < 3);
+        let st_words = self.to_words();
+        [
+            st_words[4 * y],
+            st_words[4 * y + 1],
+            st_words[4 * y + 2],
+            st_words[4 * y + 3],
+        ]
+    }
+
+    #[inline]
+    pub fn set_plane(&mut self, y: usize, plane: [u32; 4]) {
+        assert!(y < 3);
+        let mut st_words = self.to_words();
+        st_words[4 * y..4 * y + 4].copy_from_slice(&plane);
+        self.init_from_words(st_words);
+    }
+
+    #[inline]
+    pub fn bit(&self, x: usize, y: usize, z: usize) -> bool {
+        assert!(z < 32);
+        (self.lane(x, y) >> z) & 1 != 0
+    }
+
+    #[inline]
+    pub fn set_bit(&mut self, x: usize, y: usize, z: usize, bit: bool) {
+        assert!(z < 32);
+        let lane = self.lane(x, y) & !(1 << z);
+        self.set_lane(x, y, lane | ((bit as u32) << z));
+    }
+
+    pub fn theta(&mut self) {
+        let mut st_words = self.to_words();
+        let mut p = [0u32; 4];
+        for (x, p) in p.iter_mut().enumerate() {
+            *p = st_words[x] ^ st_words[4 + x] ^ st_words[8 + x];
+        }
+        for (i, st_word) in st_words.iter_mut().enumerate() {
+            let p = p[(i + 3) % 4];
+            *st_word ^= p.rotate_left(5) ^ p.rotate_left(14);
+        }
+        self.init_from_words(st_words);
+    }
+
+    pub fn rho_west(&mut self) {
+        let mut st_words = self.to_words();
+        st_words[4..8].rotate_right(1);
+        for st_word in &mut st_words[8..12] {
+            *st_word = st_word.rotate_left(11);
+        }
+        self.init_from_words(st_words);
+    }
+
+    pub fn iota(&mut self, round_key: u32) {
+        let mut st_words = self.to_words();
+        st_words[0] ^= round_key;
+        self.init_from_words(st_words);
+    }
+
+    pub fn chi(&mut self) {
+        let a = self.to_words();
+        let mut st_words = a;
+        for (i, st_word) in st_words.iter_mut().enumerate() {
+            *st_word ^= !a[(i + 4) % 12] & a[(i + 8) % 12];
+        }
+        self.init_from_words(st_words);
+    }
+
+    pub fn rho_east(&mut self) {
+        let mut st_words = self.to_words();
+        for st_word in &mut st_words[4..8] {
+            *st_word = st_word.rotate_left(1);
+        }
+        st_words[8..12].rotate_right(2);
+        for st_word in &mut st_words[8..12] {
+            *st_word = st_word.rotate_left(8);
+        }
+        self.init_from_words(st_words);
+    }
+
+    pub fn permute_traced<F>(&mut self, trace: F)
+    where
+        F: FnMut(usize, XoodooStep, &Xoodoo),
+    {
+        self.permute_rounds_traced(XOODOO_MAX_ROUNDS, trace)
+    }
+
+    pub fn permute_rounds_traced<F>(&mut self, rounds: usize, mut trace: F)
+    where
+        F: FnMut(usize, XoodooStep, &Xoodoo),
+    {
+        for (round, &round_key) in last_round_keys(rounds).iter().enumerate() {
+            for &step in &XoodooStep::ROUND {
+                match step {
+                    XoodooStep::Theta => self.theta(),
+                    XoodooStep::RhoWest => self.rho_west(),
+                    XoodooStep::Iota => self.iota(round_key),
+                    XoodooStep::Chi => self.chi(),
+                    XoodooStep::RhoEast => self.rho_east(),
+                }
+                trace(round, step, self);
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use zeroize::Zeroize;
+
+use super::internal::Phase;
+use super::*;
+
+#[derive(Clone, Debug)]
+struct Pending {
+    buf: [u8; MAX_STATE_BYTES],
+    len: usize,
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        self.buf.zeroize();
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct XoodyakAbsorber<T: XoodyakCommon> {
+    inner: T,
+    pending: Pending,
+    started: bool,
+}
+
+impl<T: XoodyakCommon> XoodyakAbsorber<T> {
+    pub fn new(inner: T) -> Self {
+        XoodyakAbsorber {
+            inner,
+            pending: Pending {
+                buf: [0u8; MAX_STATE_BYTES],
+                len: 0,
+            },
+            started: false,
+        }
+    }
+
+    #[inline]
+    fn absorb_block(&mut self, block: &[u8]) {
+        if self.started {
+            self.inner.up(None, 0x00);
+            self.inner.down(Some(block), 0x00);
+        } else {
+            if self.inner.phase() != Phase::Up {
+                self.inner.up(None, 0x00);
+            }
+            self.inner.down(Some(block), 0x03);
+            self.started = true;
+        }
+    }
+
+    fn absorb_pending(&mut self) {
+        let pending = self.pending.clone();
+        self.absorb_block(&pending.buf[..pending.len]);
+        self.pending.len = 0;
+    }
+
+    pub fn update(&mut self, bin: &[u8]) {
+        let rate = self.inner.absorb_rate();
+        let mut bin = bin;
+        if self.pending.len > 0 {
+            if self.pending.len == rate && !bin.is_empty() {
+                self.absorb_pending();
+            } else {
+                let len = bin.len().min(rate - self.pending.len);
+                self.pending.buf[self.pending.len..self.pending.len + len]
+                    .copy_from_slice(&bin[..len]);
+                self.pending.len += len;
+                bin = &bin[len..];
+                if bin.is_empty() {
+                    return;
+                }
+                self.absorb_pending();
+            }
+        }
+        while bin.len() > rate {
+            let (block, rest) = bin.split_at(rate);
+            self.absorb_block(block);
+            bin = rest;
+        }
+        self.pending.buf[..bin.len()].copy_from_slice(bin);
+        self.pending.len = bin.len();
+    }
+
+    pub fn finalize(mut self) -> T {
+        if self.pending.len > 0 || !self.started {
+            self.absorb_pending();
+        }
+        self.inner
+    }
+}
+
+impl<T: XoodyakCommon + Default> Default for XoodyakAbsorber<T> {
+    #[inline]
+    fn default() -> Self {
+        XoodyakAbsorber::new(T::default())
+    }
+}
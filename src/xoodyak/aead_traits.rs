@@ -0,0 +1,70 @@
+use aead::consts::{U0, U16};
+use aead::{AeadCore, AeadInPlace, Key, KeyInit, KeySizeUser, Nonce};
+use zeroize::Zeroize;
+
+use super::*;
+
+const AEAD_KEY_BYTES: usize = 16;
+
+#[derive(Clone)]
+pub struct XoodyakAead {
+    key: [u8; AEAD_KEY_BYTES],
+}
+
+impl XoodyakAead {
+    #[inline]
+    fn keyed(&self, nonce: &Nonce<Self>) -> XoodyakKeyed {
+        XoodyakKeyed::new_aead(&self.key, nonce).unwrap()
+    }
+}
+
+impl KeySizeUser for XoodyakAead {
+    type KeySize = U16;
+}
+
+impl KeyInit for XoodyakAead {
+    #[inline]
+    fn new(key: &Key<Self>) -> Self {
+        XoodyakAead { key: (*key).into() }
+    }
+}
+
+impl AeadCore for XoodyakAead {
+    type NonceSize = U16;
+    type TagSize = U16;
+    type CiphertextOverhead = U0;
+}
+
+impl AeadInPlace for XoodyakAead {
+    fn encrypt_in_place_detached(
+        &self,
+        nonce: &Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> aead::Result<aead::Tag<Self>> {
+        let auth_tag = self
+            .keyed(nonce)
+            .aead_encrypt_in_place_detached_with_ad(buffer, Some(associated_data));
+        let auth_tag: [u8; AUTH_TAG_BYTES] = auth_tag.into();
+        Ok(auth_tag.into())
+    }
+
+    fn decrypt_in_place_detached(
+        &self,
+        nonce: &Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &aead::Tag<Self>,
+    ) -> aead::Result<()> {
+        let auth_tag = Tag::from(<[u8; AUTH_TAG_BYTES]>::from(*tag));
+        self.keyed(nonce)
+            .aead_decrypt_in_place_detached_with_ad(buffer, &auth_tag, Some(associated_data))
+            .map_err(|_| aead::Error)
+    }
+}
+
+impl Drop for XoodyakAead {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
@@ -8,6 +8,8 @@ pub enum XoodyakAny {
 }
 
 impl internal::XoodyakCommon for XoodyakAny {
+    type State = Xoodoo;
+
     fn state(&mut self) -> &mut Xoodoo {
         match self {
             XoodyakAny::Hash(x) => x.state(),
@@ -154,6 +156,88 @@ impl XoodyakAny {
         self.keyed()?.aead_decrypt_in_place(in_out)
     }
 
+    #[inline]
+    pub fn aead_encrypt_detached_with_ad(
+        &mut self,
+        out: &mut [u8],
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<Tag, Error> {
+        self.keyed()?.aead_encrypt_detached_with_ad(out, bin, ad)
+    }
+
+    #[inline]
+    pub fn aead_encrypt_with_ad(
+        &mut self,
+        out: &mut [u8],
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        self.keyed()?.aead_encrypt_with_ad(out, bin, ad)
+    }
+
+    #[inline]
+    pub fn aead_decrypt_detached_with_ad(
+        &mut self,
+        out: &mut [u8],
+        auth_tag: &Tag,
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        self.keyed()?
+            .aead_decrypt_detached_with_ad(out, auth_tag, bin, ad)
+    }
+
+    #[inline]
+    pub fn aead_decrypt_with_ad(
+        &mut self,
+        out: &mut [u8],
+        bin: &[u8],
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        self.keyed()?.aead_decrypt_with_ad(out, bin, ad)
+    }
+
+    #[inline]
+    pub fn aead_encrypt_in_place_detached_with_ad(
+        &mut self,
+        in_out: &mut [u8],
+        ad: Option<&[u8]>,
+    ) -> Result<Tag, Error> {
+        Ok(self
+            .keyed()?
+            .aead_encrypt_in_place_detached_with_ad(in_out, ad))
+    }
+
+    #[inline]
+    pub fn aead_encrypt_in_place_with_ad(
+        &mut self,
+        in_out: &mut [u8],
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        self.keyed()?.aead_encrypt_in_place_with_ad(in_out, ad)
+    }
+
+    #[inline]
+    pub fn aead_decrypt_in_place_detached_with_ad(
+        &mut self,
+        in_out: &mut [u8],
+        auth_tag: &Tag,
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        self.keyed()?
+            .aead_decrypt_in_place_detached_with_ad(in_out, auth_tag, ad)
+    }
+
+    #[inline]
+    pub fn aead_decrypt_in_place_with_ad<'t>(
+        &mut self,
+        in_out: &'t mut [u8],
+        ad: Option<&[u8]>,
+    ) -> Result<&'t mut [u8], Error> {
+        self.keyed()?.aead_decrypt_in_place_with_ad(in_out, ad)
+    }
+
     #[cfg(feature = "std")]
     #[inline]
     pub fn encrypt_to_vec(&mut self, bin: &[u8]) -> Result<Vec<u8>, Error> {
@@ -210,6 +294,71 @@ impl XoodyakAny {
     pub fn aead_decrypt_in_place_to_vec(&mut self, in_out: Vec<u8>) -> Result<Vec<u8>, Error> {
         self.keyed()?.aead_decrypt_in_place_to_vec(in_out)
     }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn aead_encrypt_to_vec_detached_with_ad(
+        &mut self,
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<(Vec<u8>, Tag), Error> {
+        self.keyed()?.aead_encrypt_to_vec_detached_with_ad(bin, ad)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn aead_encrypt_to_vec_with_ad(
+        &mut self,
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        self.keyed()?.aead_encrypt_to_vec_with_ad(bin, ad)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn aead_encrypt_in_place_to_vec_with_ad(
+        &mut self,
+        in_out: Vec<u8>,
+        ad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        Ok(self
+            .keyed()?
+            .aead_encrypt_in_place_to_vec_with_ad(in_out, ad))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn aead_decrypt_to_vec_detached_with_ad(
+        &mut self,
+        auth_tag: Tag,
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        self.keyed()?
+            .aead_decrypt_to_vec_detached_with_ad(auth_tag, bin, ad)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn aead_decrypt_to_vec_with_ad(
+        &mut self,
+        bin: &[u8],
+        ad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        self.keyed()?.aead_decrypt_to_vec_with_ad(bin, ad)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn aead_decrypt_in_place_to_vec_with_ad(
+        &mut self,
+        in_out: Vec<u8>,
+        ad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        self.keyed()?
+            .aead_decrypt_in_place_to_vec_with_ad(in_out, ad)
+    }
 }
 
 impl XoodyakCommon for XoodyakAny {}
@@ -0,0 +1,156 @@
+use zeroize::Zeroize;
+
+use super::internal::XoodyakCommon as _;
+use super::*;
+
+#[derive(Clone, Debug)]
+struct Block {
+    keystream: [u8; KEYED_SQUEEZE_RATE],
+    plaintext: [u8; KEYED_SQUEEZE_RATE],
+    len: usize,
+}
+
+impl Drop for Block {
+    fn drop(&mut self) {
+        self.keystream.zeroize();
+        self.plaintext.zeroize();
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Crypter {
+    keyed: XoodyakKeyed,
+    block: Block,
+    started: bool,
+}
+
+impl Crypter {
+    fn new(keyed: XoodyakKeyed) -> Self {
+        Crypter {
+            keyed,
+            block: Block {
+                keystream: [0u8; KEYED_SQUEEZE_RATE],
+                plaintext: [0u8; KEYED_SQUEEZE_RATE],
+                len: 0,
+            },
+            started: false,
+        }
+    }
+
+    fn update_in_place(&mut self, in_out: &mut [u8], decrypt: bool) {
+        let mut in_out = in_out;
+        while !in_out.is_empty() {
+            if !self.started || self.block.len == KEYED_SQUEEZE_RATE {
+                let cu = if self.started { 0x00 } else { 0x80 };
+                self.keyed.up(Some(&mut self.block.keystream), cu);
+                self.block.len = 0;
+                self.started = true;
+            }
+            let offset = self.block.len;
+            let len = in_out.len().min(KEYED_SQUEEZE_RATE - offset);
+            let (chunk, rest) = in_out.split_at_mut(len);
+            for ((x, k), p) in chunk
+                .iter_mut()
+                .zip(&self.block.keystream[offset..])
+                .zip(&mut self.block.plaintext[offset..])
+            {
+                if decrypt {
+                    *x ^= *k;
+                    *p = *x;
+                } else {
+                    *p = *x;
+                    *x ^= *k;
+                }
+            }
+            self.block.len += len;
+            if self.block.len == KEYED_SQUEEZE_RATE {
+                self.keyed.down(Some(&self.block.plaintext), 0x00);
+            }
+            in_out = rest;
+        }
+    }
+
+    fn update(&mut self, out: &mut [u8], bin: &[u8], decrypt: bool) -> Result<(), Error> {
+        if out.len() < bin.len() {
+            return Err(Error::InvalidBufferLength);
+        }
+        let out = &mut out[..bin.len()];
+        out.copy_from_slice(bin);
+        self.update_in_place(out, decrypt);
+        Ok(())
+    }
+
+    fn finish(mut self) -> XoodyakKeyed {
+        if self.started && self.block.len < KEYED_SQUEEZE_RATE {
+            let len = self.block.len;
+            self.keyed.down(Some(&self.block.plaintext[..len]), 0x00);
+        }
+        self.keyed
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct XoodyakEncryptor(Crypter);
+
+impl XoodyakEncryptor {
+    pub fn new(keyed: XoodyakKeyed) -> Self {
+        XoodyakEncryptor(Crypter::new(keyed))
+    }
+
+    #[inline]
+    pub fn update(&mut self, out: &mut [u8], bin: &[u8]) -> Result<(), Error> {
+        self.0.update(out, bin, false)
+    }
+
+    #[inline]
+    pub fn update_in_place(&mut self, in_out: &mut [u8]) {
+        self.0.update_in_place(in_out, false)
+    }
+
+    #[inline]
+    pub fn finish(self) -> XoodyakKeyed {
+        self.0.finish()
+    }
+
+    pub fn finalize(self) -> Tag {
+        let mut keyed = self.finish();
+        let mut auth_tag = Tag::default();
+        keyed.squeeze(auth_tag.inner_mut());
+        auth_tag
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct XoodyakDecryptor(Crypter);
+
+impl XoodyakDecryptor {
+    pub fn new(keyed: XoodyakKeyed) -> Self {
+        XoodyakDecryptor(Crypter::new(keyed))
+    }
+
+    #[inline]
+    pub fn update(&mut self, out: &mut [u8], bin: &[u8]) -> Result<(), Error> {
+        self.0.update(out, bin, true)
+    }
+
+    #[inline]
+    pub fn update_in_place(&mut self, in_out: &mut [u8]) {
+        self.0.update_in_place(in_out, true)
+    }
+
+    #[inline]
+    pub fn finish(self) -> XoodyakKeyed {
+        self.0.finish()
+    }
+
+    pub fn finalize(self, auth_tag: &Tag) -> Result<(), Error> {
+        let mut keyed = self.finish();
+        let mut computed_tag = Tag::default();
+        keyed.squeeze(computed_tag.inner_mut());
+        if computed_tag == *auth_tag {
+            Ok(())
+        } else {
+            Err(Error::TagMismatch)
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use digest::consts::U32;
+use digest::{
+    ExtendableOutput, ExtendableOutputReset, FixedOutput, FixedOutputReset, HashMarker, Output,
+    OutputSizeUser, Reset, Update, XofReader,
+};
+
+use super::*;
+
+#[derive(Clone, Debug, Default)]
+pub struct XoodyakDigest(XoodyakAbsorber<XoodyakHash>);
+
+impl XoodyakDigest {
+    #[inline]
+    fn finish(self) -> XoodyakHash {
+        self.0.finalize()
+    }
+}
+
+impl HashMarker for XoodyakDigest {}
+
+impl OutputSizeUser for XoodyakDigest {
+    type OutputSize = U32;
+}
+
+impl Update for XoodyakDigest {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+}
+
+impl Reset for XoodyakDigest {
+    #[inline]
+    fn reset(&mut self) {
+        *self = XoodyakDigest::default();
+    }
+}
+
+impl FixedOutput for XoodyakDigest {
+    #[inline]
+    fn finalize_into(self, out: &mut Output<Self>) {
+        self.finish().squeeze(out);
+    }
+}
+
+impl FixedOutputReset for XoodyakDigest {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        core::mem::take(self).finish().squeeze(out);
+    }
+}
+
+impl ExtendableOutput for XoodyakDigest {
+    type Reader = XoodyakXofReader;
+
+    #[inline]
+    fn finalize_xof(self) -> Self::Reader {
+        XoodyakXofReader::new(self.finish())
+    }
+}
+
+impl ExtendableOutputReset for XoodyakDigest {
+    #[inline]
+    fn finalize_xof_reset(&mut self) -> Self::Reader {
+        XoodyakXofReader::new(core::mem::take(self).finish())
+    }
+}
+
+impl XofReader for XoodyakXofReader {
+    #[inline]
+    fn read(&mut self, buffer: &mut [u8]) {
+        XoodyakXofReader::read(self, buffer)
+    }
+}
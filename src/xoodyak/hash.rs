@@ -2,30 +2,113 @@ use super::internal::{Mode, Phase};
 use super::*;
 
 #[derive(Clone, Debug)]
-pub struct XoodyakHash {
-    state: Xoodoo,
+pub struct CyclistHash<P: Permutation = Xoodoo> {
+    state: P,
     phase: Phase,
 }
 
-impl XoodyakHash {
+pub type XoodyakHash = CyclistHash<Xoodoo>;
+
+impl<P: Permutation> CyclistHash<P> {
     pub fn new() -> Self {
-        XoodyakHash {
-            state: Xoodoo::default(),
+        assert!(P::STATE_BYTES <= MAX_STATE_BYTES);
+        CyclistHash {
+            state: P::default(),
             phase: Phase::Up,
         }
     }
 }
 
-impl Default for XoodyakHash {
+#[derive(Clone, Copy)]
+struct HashLane {
+    input: usize,
+    offset: usize,
+    squeezed: bool,
+}
+
+impl CyclistHash<Xoodoo> {
+    pub fn hash_many(inputs: &[&[u8]], outputs: &mut [[u8; 32]]) -> Result<(), Error> {
+        const LANES: usize = XoodooX16::STATES;
+        const RATE: usize = <Xoodoo as Permutation>::HASH_ABSORB_RATE;
+        const HALF: usize = <Xoodoo as Permutation>::HASH_SQUEEZE_RATE;
+        const STATE_BYTES: usize = <Xoodoo as Permutation>::STATE_BYTES;
+
+        if outputs.len() < inputs.len() {
+            return Err(Error::InvalidBufferLength);
+        }
+        // Idle lanes are permuted too, which only pays off with SIMD lanes and a few inputs
+        if inputs.len() <= 2 || XoodooMultiBackend::detect(LANES) == XoodooMultiBackend::Portable {
+            for (bin, out) in inputs.iter().zip(outputs.iter_mut()) {
+                let mut st = XoodyakHash::new();
+                st.absorb(bin);
+                st.squeeze(out);
+            }
+            return Ok(());
+        }
+        let mut multi = XoodooX16::new();
+        let mut lanes: [Option<HashLane>; LANES] = [None; LANES];
+        let mut next_input = 0;
+        loop {
+            for (i, lane) in lanes.iter_mut().enumerate() {
+                if lane.is_some() || next_input == inputs.len() {
+                    continue;
+                }
+                // absorb() from a fresh state: the first block goes in without a permutation,
+                // followed by the low bit of the 0x03 color
+                let bin = inputs[next_input];
+                let len = bin.len().min(RATE);
+                multi.set_state(i, &Xoodoo::default());
+                multi.add_bytes(i, &bin[..len]);
+                multi.add_byte(i, 0x01, len);
+                multi.add_byte(i, 0x01, STATE_BYTES - 1);
+                *lane = Some(HashLane {
+                    input: next_input,
+                    offset: len,
+                    squeezed: false,
+                });
+                next_input += 1;
+            }
+            if lanes.iter().all(Option::is_none) {
+                return Ok(());
+            }
+            multi.permute();
+            for (i, slot) in lanes.iter_mut().enumerate() {
+                let lane = match slot {
+                    Some(lane) => lane,
+                    None => continue,
+                };
+                let bin = inputs[lane.input];
+                let out = &mut outputs[lane.input];
+                if lane.offset < bin.len() {
+                    let len = (bin.len() - lane.offset).min(RATE);
+                    multi.add_bytes(i, &bin[lane.offset..][..len]);
+                    multi.add_byte(i, 0x01, len);
+                    lane.offset += len;
+                } else if !lane.squeezed {
+                    multi.extract_bytes(i, &mut out[..HALF]);
+                    multi.add_byte(i, 0x01, 0);
+                    lane.squeezed = true;
+                } else {
+                    multi.extract_bytes(i, &mut out[HALF..]);
+                    *slot = None;
+                }
+            }
+        }
+    }
+}
+
+impl<P: Permutation> Default for CyclistHash<P> {
     #[inline]
     fn default() -> Self {
-        XoodyakHash::new()
+        CyclistHash::new()
     }
 }
 
-impl internal::XoodyakCommon for XoodyakHash {
+impl<P: Permutation> internal::XoodyakCommon for CyclistHash<P> {
+    type State = P;
+
     #[inline(always)]
-    fn state(&mut self) -> &mut Xoodoo {
+    fn state(&mut self) -> &mut P {
         &mut self.state
     }
 
@@ -46,13 +129,13 @@ impl internal::XoodyakCommon for XoodyakHash {
 
     #[inline(always)]
     fn absorb_rate(&self) -> usize {
-        HASH_ABSORB_RATE
+        P::HASH_ABSORB_RATE
     }
 
     #[inline(always)]
     fn squeeze_rate(&self) -> usize {
-        HASH_SQUEEZE_RATE
+        P::HASH_SQUEEZE_RATE
     }
 }
 
-impl XoodyakCommon for XoodyakHash {}
+impl<P: Permutation> XoodyakCommon for CyclistHash<P> {}
@@ -0,0 +1,424 @@
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::{Deref, DerefMut};
+
+use zeroize::Zeroize;
+
+use super::*;
+
+const IO_BUFFER_BYTES: usize = 4096;
+
+impl<T: XoodyakCommon> Write for XoodyakAbsorber<T> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<T: XoodyakCommon> Read for XoodyakXofReader<T> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        XoodyakXofReader::read(self, buf);
+        Ok(buf.len())
+    }
+}
+
+#[derive(Debug)]
+pub struct XoodyakEncryptWriter<W: Write> {
+    inner: W,
+    encryptor: XoodyakEncryptor,
+    desynchronized: bool,
+}
+
+impl<W: Write> XoodyakEncryptWriter<W> {
+    pub fn new(inner: W, keyed: XoodyakKeyed) -> Self {
+        XoodyakEncryptWriter {
+            inner,
+            encryptor: XoodyakEncryptor::new(keyed),
+            desynchronized: false,
+        }
+    }
+
+    #[inline]
+    pub fn is_desynchronized(&self) -> bool {
+        self.desynchronized
+    }
+
+    #[inline]
+    fn check(&self) -> io::Result<()> {
+        if self.desynchronized {
+            return Err(io::Error::other(Error::Desynchronized));
+        }
+        Ok(())
+    }
+
+    pub fn finalize_detached(self) -> io::Result<(W, Tag)> {
+        self.check()?;
+        Ok((self.inner, self.encryptor.finalize()))
+    }
+
+    pub fn finalize(self) -> io::Result<W> {
+        let (mut inner, auth_tag) = self.finalize_detached()?;
+        inner.write_all(auth_tag.as_ref())?;
+        Ok(inner)
+    }
+}
+
+impl<W: Write> Write for XoodyakEncryptWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.check()?;
+        let mut tmp = [0u8; IO_BUFFER_BYTES];
+        let len = buf.len().min(IO_BUFFER_BYTES);
+        tmp[..len].copy_from_slice(&buf[..len]);
+        self.encryptor.update_in_place(&mut tmp[..len]);
+        if let Err(e) = self.inner.write_all(&tmp[..len]) {
+            self.desynchronized = true;
+            return Err(e);
+        }
+        Ok(len)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Decrypts a ciphertext followed by its tag.
+///
+/// Plaintext is returned as it is decrypted, before the tag has been
+/// verified. The tag is only checked at the end of the input, and a mismatch
+/// is reported as an error that every later `read()` returns again; the data
+/// read up to that point must then be discarded. `XoodyakStreamReader` only
+/// returns plaintext after the tag of the chunk it comes from has been verified.
+#[derive(Debug)]
+pub struct XoodyakDecryptReader<R: Read> {
+    inner: R,
+    decryptor: Option<XoodyakDecryptor>,
+    tail: [u8; AUTH_TAG_BYTES],
+    tail_len: usize,
+    error: Option<(io::ErrorKind, Error)>,
+}
+
+impl<R: Read> XoodyakDecryptReader<R> {
+    pub fn new(inner: R, keyed: XoodyakKeyed) -> Self {
+        XoodyakDecryptReader {
+            inner,
+            decryptor: Some(XoodyakDecryptor::new(keyed)),
+            tail: [0u8; AUTH_TAG_BYTES],
+            tail_len: 0,
+            error: None,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn verify(&mut self) -> io::Result<()> {
+        let decryptor = match self.decryptor.take() {
+            None => return Ok(()),
+            Some(decryptor) => decryptor,
+        };
+        let res = if self.tail_len != AUTH_TAG_BYTES {
+            Err((io::ErrorKind::UnexpectedEof, Error::InvalidBufferLength))
+        } else {
+            decryptor
+                .finalize(&Tag::from(self.tail))
+                .map_err(|e| (io::ErrorKind::InvalidData, e))
+        };
+        if let Err((kind, e)) = res {
+            self.error = Some((kind, e));
+            return Err(io::Error::new(kind, e));
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for XoodyakDecryptReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some((kind, e)) = self.error {
+            return Err(io::Error::new(kind, e));
+        }
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut tmp = [0u8; AUTH_TAG_BYTES + IO_BUFFER_BYTES];
+        let max_len = buf.len().min(IO_BUFFER_BYTES);
+        loop {
+            let decryptor = match self.decryptor.as_mut() {
+                None => return Ok(0),
+                Some(decryptor) => decryptor,
+            };
+            tmp[..self.tail_len].copy_from_slice(&self.tail[..self.tail_len]);
+            let n = self
+                .inner
+                .read(&mut tmp[self.tail_len..AUTH_TAG_BYTES + max_len])?;
+            if n == 0 {
+                self.verify()?;
+                return Ok(0);
+            }
+            let total = self.tail_len + n;
+            let len = total.saturating_sub(AUTH_TAG_BYTES);
+            decryptor.update_in_place(&mut tmp[..len]);
+            buf[..len].copy_from_slice(&tmp[..len]);
+            self.tail_len = total - len;
+            self.tail[..self.tail_len].copy_from_slice(&tmp[len..total]);
+            if len > 0 {
+                return Ok(len);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ChunkBuffer(Vec<u8>);
+
+impl Deref for ChunkBuffer {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl DerefMut for ChunkBuffer {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl Drop for ChunkBuffer {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+fn stream_io_error(e: Error) -> io::Error {
+    match e {
+        Error::InvalidBufferLength => io::Error::new(io::ErrorKind::UnexpectedEof, e),
+        _ => io::Error::new(io::ErrorKind::InvalidData, e),
+    }
+}
+
+#[derive(Debug)]
+pub struct XoodyakStreamWriter<W: Write> {
+    inner: W,
+    encryptor: XoodyakStreamEncryptor,
+    buf: ChunkBuffer,
+    len: usize,
+}
+
+impl<W: Write> XoodyakStreamWriter<W> {
+    pub fn new(inner: W, encryptor: XoodyakStreamEncryptor) -> Self {
+        let buf = ChunkBuffer(vec![0u8; encryptor.chunk_bytes() + AUTH_TAG_BYTES]);
+        XoodyakStreamWriter {
+            inner,
+            encryptor,
+            buf,
+            len: 0,
+        }
+    }
+
+    fn write_chunk(&mut self, last: bool) -> io::Result<()> {
+        let chunk = &mut self.buf[..self.len + AUTH_TAG_BYTES];
+        self.encryptor
+            .seal_chunk(chunk, last)
+            .map_err(stream_io_error)?;
+        self.inner.write_all(chunk)?;
+        self.len = 0;
+        Ok(())
+    }
+
+    pub fn finalize(mut self) -> io::Result<W> {
+        self.write_chunk(true)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for XoodyakStreamWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let chunk_bytes = self.encryptor.chunk_bytes();
+        if self.len == chunk_bytes {
+            self.write_chunk(false)?;
+        }
+        let len = buf.len().min(chunk_bytes - self.len);
+        self.buf[self.len..self.len + len].copy_from_slice(&buf[..len]);
+        self.len += len;
+        Ok(len)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[derive(Debug)]
+pub struct XoodyakStreamReader<R: Read> {
+    inner: R,
+    decryptor: XoodyakStreamDecryptor,
+    buf: ChunkBuffer,
+    next: Option<u8>,
+    pos: usize,
+    end: usize,
+}
+
+impl<R: Read> XoodyakStreamReader<R> {
+    pub fn new(inner: R, decryptor: XoodyakStreamDecryptor) -> Self {
+        let buf = ChunkBuffer(vec![0u8; decryptor.chunk_bytes() + AUTH_TAG_BYTES]);
+        XoodyakStreamReader {
+            inner,
+            decryptor,
+            buf,
+            next: None,
+            pos: 0,
+            end: 0,
+        }
+    }
+
+    fn fill(&mut self, buf_len: usize) -> io::Result<usize> {
+        let mut len = 0;
+        if let Some(next) = self.next.take() {
+            self.buf[0] = next;
+            len = 1;
+        }
+        while len < buf_len {
+            match self.inner.read(&mut self.buf[len..buf_len]) {
+                Ok(0) => break,
+                Ok(n) => len += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(len)
+    }
+
+    fn read_chunk(&mut self) -> io::Result<()> {
+        let buf_len = self.buf.len();
+        let len = self.fill(buf_len)?;
+        let mut last = len < buf_len;
+        if !last {
+            let mut next = [0u8; 1];
+            loop {
+                match self.inner.read(&mut next) {
+                    Ok(0) => last = true,
+                    Ok(_) => self.next = Some(next[0]),
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                }
+                break;
+            }
+        }
+        let pt = self
+            .decryptor
+            .open_chunk(&mut self.buf[..len], last)
+            .map_err(stream_io_error)?;
+        self.pos = 0;
+        self.end = pt.len();
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for XoodyakStreamReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.end {
+            if buf.is_empty() || self.decryptor.is_finished() {
+                return Ok(0);
+            }
+            self.read_chunk()?;
+        }
+        let len = buf.len().min(self.end - self.pos);
+        buf[..len].copy_from_slice(&self.buf[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+#[derive(Debug)]
+pub struct XoodyakStreamSeekReader<R: Read + Seek> {
+    inner: R,
+    access: XoodyakStreamRandomAccess,
+    buf: ChunkBuffer,
+    cached: Option<(u64, usize)>,
+}
+
+impl<R: Read + Seek> XoodyakStreamSeekReader<R> {
+    pub fn new(
+        mut inner: R,
+        key: &[u8],
+        nonce: &[u8],
+        ad: Option<&[u8]>,
+        chunk_bytes: usize,
+    ) -> io::Result<Self> {
+        let ciphertext_len = inner.seek(SeekFrom::End(0))?;
+        let access = XoodyakStreamRandomAccess::new(key, nonce, ad, chunk_bytes, ciphertext_len)
+            .map_err(stream_io_error)?;
+        let buf = ChunkBuffer(vec![0u8; chunk_bytes + AUTH_TAG_BYTES]);
+        Ok(XoodyakStreamSeekReader {
+            inner,
+            access,
+            buf,
+            cached: None,
+        })
+    }
+
+    #[inline]
+    pub fn plaintext_len(&self) -> u64 {
+        self.access.plaintext_len()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn load_chunk(&mut self, index: u64) -> io::Result<usize> {
+        if let Some((cached_index, pt_len)) = self.cached {
+            if cached_index == index {
+                return Ok(pt_len);
+            }
+        }
+        self.cached = None;
+        let (offset, len) = self
+            .access
+            .chunk_ciphertext_range(index)
+            .map_err(stream_io_error)?;
+        self.inner.seek(SeekFrom::Start(offset))?;
+        self.inner.read_exact(&mut self.buf[..len])?;
+        let pt_len = self
+            .access
+            .open_chunk(index, &mut self.buf[..len])
+            .map_err(stream_io_error)?
+            .len();
+        self.cached = Some((index, pt_len));
+        Ok(pt_len)
+    }
+
+    pub fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        let chunk_bytes = self.access.chunk_bytes() as u64;
+        let plaintext_len = self.plaintext_len();
+        let mut offset = offset;
+        let mut written = 0;
+        while written < buf.len() && offset < plaintext_len {
+            let index = self.access.chunk_index(offset);
+            let pt_len = self.load_chunk(index)?;
+            let chunk_offset = (offset - index * chunk_bytes) as usize;
+            let len = (buf.len() - written).min(pt_len - chunk_offset);
+            buf[written..written + len]
+                .copy_from_slice(&self.buf[chunk_offset..chunk_offset + len]);
+            written += len;
+            offset += len as u64;
+        }
+        Ok(written)
+    }
+}
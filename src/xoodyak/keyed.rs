@@ -3,15 +3,19 @@ use super::internal::{Mode, Phase};
 use super::*;
 
 #[derive(Clone, Debug)]
-pub struct XoodyakKeyed {
-    state: Xoodoo,
+pub struct CyclistKeyed<P: Permutation = Xoodoo> {
+    state: P,
     mode: Mode,
     phase: Phase,
 }
 
-impl internal::XoodyakCommon for XoodyakKeyed {
+pub type XoodyakKeyed = CyclistKeyed<Xoodoo>;
+
+impl<P: Permutation> internal::XoodyakCommon for CyclistKeyed<P> {
+    type State = P;
+
     #[inline(always)]
-    fn state(&mut self) -> &mut Xoodoo {
+    fn state(&mut self) -> &mut P {
         &mut self.state
     }
 
@@ -32,26 +36,27 @@ impl internal::XoodyakCommon for XoodyakKeyed {
 
     #[inline(always)]
     fn absorb_rate(&self) -> usize {
-        KEYED_ABSORB_RATE
+        P::KEYED_ABSORB_RATE
     }
 
     #[inline(always)]
     fn squeeze_rate(&self) -> usize {
-        KEYED_SQUEEZE_RATE
+        P::KEYED_SQUEEZE_RATE
     }
 }
 
-impl XoodyakCommon for XoodyakKeyed {}
+impl<P: Permutation> XoodyakCommon for CyclistKeyed<P> {}
 
-impl XoodyakKeyed {
+impl<P: Permutation> CyclistKeyed<P> {
     pub fn new(
         key: &[u8],
         nonce: Option<&[u8]>,
         key_id: Option<&[u8]>,
         counter: Option<&[u8]>,
     ) -> Result<Self, Error> {
-        let mut xoodyak = XoodyakKeyed {
-            state: Xoodoo::default(),
+        assert!(P::STATE_BYTES <= MAX_STATE_BYTES);
+        let mut xoodyak = CyclistKeyed {
+            state: P::default(),
             phase: Phase::Up,
             mode: Mode::Keyed,
         };
@@ -59,6 +64,12 @@ impl XoodyakKeyed {
         Ok(xoodyak)
     }
 
+    pub fn new_aead(key: &[u8], nonce: &[u8]) -> Result<Self, Error> {
+        let mut xoodyak = Self::new(key, None, None, None)?;
+        xoodyak.absorb(nonce);
+        Ok(xoodyak)
+    }
+
     pub fn absorb_key_and_nonce(
         &mut self,
         key: &[u8],
@@ -70,10 +81,10 @@ impl XoodyakKeyed {
 
         let key_id_len = key_id.unwrap_or_default().len();
         let nonce_len = nonce.len();
-        if key.len() + 1 + key_id_len + nonce_len > KEYED_ABSORB_RATE {
+        if key.len() + 1 + key_id_len + nonce_len > P::KEYED_ABSORB_RATE {
             return Err(Error::InvalidParameterLength);
         }
-        let mut iv = [0u8; KEYED_ABSORB_RATE];
+        let mut iv = [0u8; MAX_STATE_BYTES];
         let key_len = key.len();
         iv[..key_len].copy_from_slice(key);
         let mut iv_len = key_len;
@@ -84,10 +95,10 @@ impl XoodyakKeyed {
         iv_len += t_len;
         iv[iv_len] = t_len as u8;
         iv_len += 1;
-        self.absorb_any(&iv[..iv_len], KEYED_ABSORB_RATE, 0x02);
+        self.absorb_any(&iv[..iv_len], P::KEYED_ABSORB_RATE, 0x02);
 
         if key_id.is_some() {
-            self.absorb_any(nonce, KEYED_ABSORB_RATE, 0x00);
+            self.absorb_any(nonce, P::KEYED_ABSORB_RATE, 0x00);
         }
         if let Some(counter) = counter {
             self.absorb_any(counter, 1, 0x00)
@@ -97,9 +108,19 @@ impl XoodyakKeyed {
 
     pub fn ratchet(&mut self) {
         debug_assert_eq!(self.mode(), Mode::Keyed);
-        let mut rolled_key = [0u8; RATCHET_RATE];
-        self.squeeze_any(&mut rolled_key, 0x10);
-        self.absorb_any(&rolled_key, RATCHET_RATE, 0x00);
+        let mut rolled_key = [0u8; MAX_STATE_BYTES];
+        let rolled_key = &mut rolled_key[..P::RATCHET_RATE];
+        self.squeeze_any(rolled_key, 0x10);
+        self.absorb_any(rolled_key, P::RATCHET_RATE, 0x00);
+    }
+
+    #[inline]
+    fn absorb_ad(&mut self, ad: Option<&[u8]>, msg_len: usize) {
+        self.absorb(ad.unwrap_or_default());
+        if msg_len == 0 {
+            self.up(None, 0x80);
+            self.down(None, 0x00);
+        }
     }
 
     pub fn encrypt(&mut self, out: &mut [u8], bin: &[u8]) -> Result<(), Error> {
@@ -109,15 +130,11 @@ impl XoodyakKeyed {
         }
         let mut cu = 0x80;
         for (out_chunk, chunk) in out
-            .chunks_mut(KEYED_SQUEEZE_RATE)
-            .zip(bin.chunks(KEYED_SQUEEZE_RATE))
+            .chunks_mut(P::KEYED_SQUEEZE_RATE)
+            .zip(bin.chunks(P::KEYED_SQUEEZE_RATE))
         {
-            self.up(Some(out_chunk), cu);
+            self.crypt_block(out_chunk, chunk, cu, false);
             cu = 0x00;
-            self.down(Some(chunk), 0x00);
-            for (out_chunk_byte, chunk_byte) in out_chunk.iter_mut().zip(chunk) {
-                *out_chunk_byte ^= *chunk_byte;
-            }
         }
         Ok(())
     }
@@ -129,44 +146,36 @@ impl XoodyakKeyed {
         }
         let mut cu = 0x80;
         for (out_chunk, chunk) in out
-            .chunks_mut(KEYED_SQUEEZE_RATE)
-            .zip(bin.chunks(KEYED_SQUEEZE_RATE))
+            .chunks_mut(P::KEYED_SQUEEZE_RATE)
+            .zip(bin.chunks(P::KEYED_SQUEEZE_RATE))
         {
-            self.up(Some(out_chunk), cu);
+            self.crypt_block(out_chunk, chunk, cu, true);
             cu = 0x00;
-            for (out_chunk_byte, chunk_byte) in out_chunk.iter_mut().zip(chunk) {
-                *out_chunk_byte ^= *chunk_byte;
-            }
-            self.down(Some(out_chunk), 0x00);
         }
         Ok(())
     }
 
     pub fn encrypt_in_place(&mut self, in_out: &mut [u8]) {
         debug_assert_eq!(self.mode(), Mode::Keyed);
-        let mut tmp = [0u8; KEYED_SQUEEZE_RATE];
+        let mut tmp = [0u8; MAX_STATE_BYTES];
         let mut cu = 0x80;
-        for in_out_chunk in in_out.chunks_mut(KEYED_SQUEEZE_RATE) {
-            self.up(Some(&mut tmp), cu);
+        for in_out_chunk in in_out.chunks_mut(P::KEYED_SQUEEZE_RATE) {
+            let tmp = &mut tmp[..in_out_chunk.len()];
+            tmp.copy_from_slice(in_out_chunk);
+            self.crypt_block(in_out_chunk, tmp, cu, false);
             cu = 0x00;
-            self.down(Some(in_out_chunk), 0x00);
-            for (in_out_chunk_byte, tmp_byte) in in_out_chunk.iter_mut().zip(&tmp) {
-                *in_out_chunk_byte ^= *tmp_byte;
-            }
         }
     }
 
     pub fn decrypt_in_place(&mut self, in_out: &mut [u8]) {
         debug_assert_eq!(self.mode(), Mode::Keyed);
-        let mut tmp = [0u8; KEYED_SQUEEZE_RATE];
+        let mut tmp = [0u8; MAX_STATE_BYTES];
         let mut cu = 0x80;
-        for in_out_chunk in in_out.chunks_mut(KEYED_SQUEEZE_RATE) {
-            self.up(Some(&mut tmp), cu);
+        for in_out_chunk in in_out.chunks_mut(P::KEYED_SQUEEZE_RATE) {
+            let tmp = &mut tmp[..in_out_chunk.len()];
+            tmp.copy_from_slice(in_out_chunk);
+            self.crypt_block(in_out_chunk, tmp, cu, true);
             cu = 0x00;
-            for (in_out_chunk_byte, tmp_byte) in in_out_chunk.iter_mut().zip(&tmp) {
-                *in_out_chunk_byte ^= *tmp_byte;
-            }
-            self.down(Some(in_out_chunk), 0x00);
         }
     }
 
@@ -277,6 +286,106 @@ impl XoodyakKeyed {
         Ok(ct)
     }
 
+    pub fn aead_encrypt_detached_with_ad(
+        &mut self,
+        out: &mut [u8],
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<Tag, Error> {
+        if out.len() < bin.unwrap_or_default().len() {
+            return Err(Error::InvalidBufferLength);
+        }
+        self.absorb_ad(ad, bin.unwrap_or_default().len());
+        self.aead_encrypt_detached(out, bin)
+    }
+
+    pub fn aead_encrypt_with_ad(
+        &mut self,
+        out: &mut [u8],
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        if out.len() < bin.unwrap_or_default().len() + AUTH_TAG_BYTES {
+            return Err(Error::InvalidBufferLength);
+        }
+        self.absorb_ad(ad, bin.unwrap_or_default().len());
+        self.aead_encrypt(out, bin)
+    }
+
+    pub fn aead_decrypt_detached_with_ad(
+        &mut self,
+        out: &mut [u8],
+        auth_tag: &Tag,
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        if out.len() < bin.unwrap_or_default().len() {
+            return Err(Error::InvalidBufferLength);
+        }
+        self.absorb_ad(ad, bin.unwrap_or_default().len());
+        self.aead_decrypt_detached(out, auth_tag, bin)
+    }
+
+    pub fn aead_decrypt_with_ad(
+        &mut self,
+        out: &mut [u8],
+        bin: &[u8],
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        let ct_len = bin
+            .len()
+            .checked_sub(AUTH_TAG_BYTES)
+            .ok_or(Error::InvalidBufferLength)?;
+        if out.len() < ct_len {
+            return Err(Error::InvalidBufferLength);
+        }
+        self.absorb_ad(ad, ct_len);
+        self.aead_decrypt(out, bin)
+    }
+
+    pub fn aead_encrypt_in_place_detached_with_ad(
+        &mut self,
+        in_out: &mut [u8],
+        ad: Option<&[u8]>,
+    ) -> Tag {
+        self.absorb_ad(ad, in_out.len());
+        self.aead_encrypt_in_place_detached(in_out)
+    }
+
+    pub fn aead_encrypt_in_place_with_ad(
+        &mut self,
+        in_out: &mut [u8],
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        if in_out.len() < AUTH_TAG_BYTES {
+            return Err(Error::InvalidBufferLength);
+        }
+        self.absorb_ad(ad, in_out.len() - AUTH_TAG_BYTES);
+        self.aead_encrypt_in_place(in_out)
+    }
+
+    pub fn aead_decrypt_in_place_detached_with_ad(
+        &mut self,
+        in_out: &mut [u8],
+        auth_tag: &Tag,
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        self.absorb_ad(ad, in_out.len());
+        self.aead_decrypt_in_place_detached(in_out, auth_tag)
+    }
+
+    pub fn aead_decrypt_in_place_with_ad<'t>(
+        &mut self,
+        in_out: &'t mut [u8],
+        ad: Option<&[u8]>,
+    ) -> Result<&'t mut [u8], Error> {
+        if in_out.len() < AUTH_TAG_BYTES {
+            return Err(Error::InvalidBufferLength);
+        }
+        self.absorb_ad(ad, in_out.len() - AUTH_TAG_BYTES);
+        self.aead_decrypt_in_place(in_out)
+    }
+
     #[cfg(feature = "std")]
     pub fn encrypt_to_vec(&mut self, bin: &[u8]) -> Result<Vec<u8>, Error> {
         let mut out = vec![0u8; bin.len()];
@@ -349,4 +458,71 @@ impl XoodyakKeyed {
         in_out.truncate(ct_len);
         Ok(in_out)
     }
+
+    #[cfg(feature = "std")]
+    pub fn aead_encrypt_to_vec_detached_with_ad(
+        &mut self,
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<(Vec<u8>, Tag), Error> {
+        self.absorb_ad(ad, bin.unwrap_or_default().len());
+        self.aead_encrypt_to_vec_detached(bin)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn aead_encrypt_to_vec_with_ad(
+        &mut self,
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        self.absorb_ad(ad, bin.unwrap_or_default().len());
+        self.aead_encrypt_to_vec(bin)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn aead_encrypt_in_place_to_vec_with_ad(
+        &mut self,
+        in_out: Vec<u8>,
+        ad: Option<&[u8]>,
+    ) -> Vec<u8> {
+        self.absorb_ad(ad, in_out.len());
+        self.aead_encrypt_in_place_to_vec(in_out)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn aead_decrypt_to_vec_detached_with_ad(
+        &mut self,
+        auth_tag: Tag,
+        bin: Option<&[u8]>,
+        ad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        self.absorb_ad(ad, bin.unwrap_or_default().len());
+        self.aead_decrypt_to_vec_detached(auth_tag, bin)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn aead_decrypt_to_vec_with_ad(
+        &mut self,
+        bin: &[u8],
+        ad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        if bin.len() < AUTH_TAG_BYTES {
+            return Err(Error::InvalidBufferLength);
+        }
+        self.absorb_ad(ad, bin.len() - AUTH_TAG_BYTES);
+        self.aead_decrypt_to_vec(bin)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn aead_decrypt_in_place_to_vec_with_ad(
+        &mut self,
+        in_out: Vec<u8>,
+        ad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        if in_out.len() < AUTH_TAG_BYTES {
+            return Err(Error::InvalidBufferLength);
+        }
+        self.absorb_ad(ad, in_out.len() - AUTH_TAG_BYTES);
+        self.aead_decrypt_in_place_to_vec(in_out)
+    }
 }
@@ -1,24 +1,46 @@
+mod absorber;
+#[cfg(feature = "aead")]
+mod aead_traits;
 #[allow(clippy::unit_arg)]
 mod any;
+mod crypter;
+#[cfg(feature = "digest")]
+mod digest_traits;
 mod hash;
+#[cfg(feature = "std")]
+mod io_adapters;
 mod keyed;
+mod session;
+mod stream;
 mod tag;
+mod xof;
 
+pub use absorber::*;
+#[cfg(feature = "aead")]
+pub use aead_traits::*;
 pub use any::*;
+pub use crypter::*;
+#[cfg(feature = "digest")]
+pub use digest_traits::*;
 pub use hash::*;
+#[cfg(feature = "std")]
+pub use io_adapters::*;
 pub use keyed::*;
+pub use session::*;
+pub use stream::{
+    XoodyakStreamDecryptor, XoodyakStreamEncryptor, XoodyakStreamRandomAccess,
+    STREAM_DEFAULT_CHUNK_BYTES,
+};
 pub use tag::*;
+pub use xof::*;
 
 use crate::error::*;
+use crate::permutation::*;
 use crate::xoodoo::*;
 
-pub(crate) const HASH_ABSORB_RATE: usize = 16;
-pub(crate) const HASH_SQUEEZE_RATE: usize = 16;
-pub(crate) const KEYED_ABSORB_RATE: usize = 44;
-pub(crate) const KEYED_SQUEEZE_RATE: usize = 24;
-pub(crate) const RATCHET_RATE: usize = 16;
+pub(crate) const KEYED_SQUEEZE_RATE: usize = <Xoodoo as Permutation>::KEYED_SQUEEZE_RATE;
 
-mod internal {
+pub(crate) mod internal {
     use super::*;
 
     #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -34,7 +56,9 @@ mod internal {
     }
 
     pub trait XoodyakCommon {
-        fn state(&mut self) -> &mut Xoodoo;
+        type State: Permutation;
+
+        fn state(&mut self) -> &mut Self::State;
         fn mode(&self) -> Mode;
         fn phase(&self) -> Phase;
         fn set_phase(&mut self, phase: Phase);
@@ -66,7 +90,7 @@ mod internal {
             debug_assert!(out.as_ref().map(|x| x.len()).unwrap_or(0) <= self.squeeze_rate());
             self.set_phase(Phase::Up);
             if self.mode() != Mode::Hash {
-                self.add_byte(cu, 47);
+                self.add_byte(cu, Self::State::STATE_BYTES - 1);
             }
             self.permute();
             if let Some(out) = out {
@@ -85,12 +109,28 @@ mod internal {
                 self.add_byte(0x01, 0);
             }
             if self.mode() == Mode::Hash {
-                self.add_byte(cd & 0x01, 47);
+                self.add_byte(cd & 0x01, Self::State::STATE_BYTES - 1);
             } else {
-                self.add_byte(cd, 47);
+                self.add_byte(cd, Self::State::STATE_BYTES - 1);
             }
         }
 
+        // up(None, cu) followed by down(bin, 0x00), with the output XORed with the input
+        #[inline(always)]
+        fn crypt_block(&mut self, out: &mut [u8], bin: &[u8], cu: u8, decrypt: bool) {
+            debug_assert_eq!(self.mode(), Mode::Keyed);
+            debug_assert!(bin.len() <= self.squeeze_rate());
+            self.add_byte(cu, Self::State::STATE_BYTES - 1);
+            self.permute();
+            if decrypt {
+                self.state().decrypt_bytes(out, bin);
+            } else {
+                self.state().encrypt_bytes(out, bin);
+            }
+            self.add_byte(0x01, bin.len());
+            self.set_phase(Phase::Down);
+        }
+
         #[inline]
         fn absorb_any(&mut self, bin: &[u8], rate: usize, cd: u8) {
             let mut chunks_it = bin.chunks(rate);
@@ -99,11 +139,20 @@ mod internal {
             }
             self.down(chunks_it.next(), cd);
             for chunk in chunks_it {
-                self.up(None, 0x00);
-                self.down(Some(chunk), 0x00);
+                self.absorb_block(chunk);
             }
         }
 
+        // up(None, 0x00) followed by down(bin, 0x00)
+        #[inline(always)]
+        fn absorb_block(&mut self, bin: &[u8]) {
+            debug_assert!(bin.len() <= self.absorb_rate());
+            self.permute();
+            self.add_bytes(bin);
+            self.add_byte(0x01, bin.len());
+            self.set_phase(Phase::Down);
+        }
+
         #[inline]
         fn squeeze_any(&mut self, out: &mut [u8], cu: u8) {
             let mut chunks_it = out.chunks_mut(self.squeeze_rate());
@@ -125,8 +174,7 @@ pub trait XoodyakCommon: internal::XoodyakCommon {
     #[inline]
     fn absorb_more(&mut self, bin: &[u8], rate: usize) {
         for chunk in bin.chunks(rate) {
-            self.up(None, 0x00);
-            self.down(Some(chunk), 0x00);
+            self.absorb_block(chunk);
         }
     }
 
@@ -0,0 +1,135 @@
+use super::*;
+
+#[derive(Clone, Debug)]
+pub struct XoodyakSession {
+    keyed: XoodyakKeyed,
+    messages: u64,
+    desynchronized: bool,
+}
+
+impl XoodyakSession {
+    pub fn new(keyed: XoodyakKeyed) -> Self {
+        XoodyakSession {
+            keyed,
+            messages: 0,
+            desynchronized: false,
+        }
+    }
+
+    #[inline]
+    pub fn messages(&self) -> u64 {
+        self.messages
+    }
+
+    #[inline]
+    pub fn is_desynchronized(&self) -> bool {
+        self.desynchronized
+    }
+
+    #[inline]
+    fn check(&self) -> Result<(), Error> {
+        if self.desynchronized {
+            return Err(Error::Desynchronized);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn opened(&mut self, res: Result<(), Error>) -> Result<(), Error> {
+        match res {
+            Ok(()) => {
+                self.messages += 1;
+                Ok(())
+            }
+            Err(Error::TagMismatch) => {
+                self.desynchronized = true;
+                Err(Error::TagMismatch)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn seal_detached(
+        &mut self,
+        out: &mut [u8],
+        ad: Option<&[u8]>,
+        msg: &[u8],
+    ) -> Result<Tag, Error> {
+        self.check()?;
+        let auth_tag = self
+            .keyed
+            .aead_encrypt_detached_with_ad(out, Some(msg), ad)?;
+        self.messages += 1;
+        Ok(auth_tag)
+    }
+
+    pub fn seal(&mut self, out: &mut [u8], ad: Option<&[u8]>, msg: &[u8]) -> Result<(), Error> {
+        self.check()?;
+        self.keyed.aead_encrypt_with_ad(out, Some(msg), ad)?;
+        self.messages += 1;
+        Ok(())
+    }
+
+    pub fn seal_in_place(&mut self, in_out: &mut [u8], ad: Option<&[u8]>) -> Result<(), Error> {
+        self.check()?;
+        self.keyed.aead_encrypt_in_place_with_ad(in_out, ad)?;
+        self.messages += 1;
+        Ok(())
+    }
+
+    pub fn open_detached(
+        &mut self,
+        out: &mut [u8],
+        auth_tag: &Tag,
+        ad: Option<&[u8]>,
+        ct: &[u8],
+    ) -> Result<(), Error> {
+        self.check()?;
+        let res = self
+            .keyed
+            .aead_decrypt_detached_with_ad(out, auth_tag, Some(ct), ad);
+        self.opened(res)
+    }
+
+    pub fn open(&mut self, out: &mut [u8], ad: Option<&[u8]>, ct: &[u8]) -> Result<(), Error> {
+        self.check()?;
+        let res = self.keyed.aead_decrypt_with_ad(out, ct, ad);
+        self.opened(res)
+    }
+
+    pub fn open_in_place<'t>(
+        &mut self,
+        in_out: &'t mut [u8],
+        ad: Option<&[u8]>,
+    ) -> Result<&'t mut [u8], Error> {
+        self.check()?;
+        let ct_len = in_out
+            .len()
+            .checked_sub(AUTH_TAG_BYTES)
+            .ok_or(Error::InvalidBufferLength)?;
+        let res = self
+            .keyed
+            .aead_decrypt_in_place_with_ad(in_out, ad)
+            .map(|_| ());
+        self.opened(res)?;
+        Ok(&mut in_out[..ct_len])
+    }
+
+    #[cfg(feature = "std")]
+    pub fn seal_to_vec(&mut self, ad: Option<&[u8]>, msg: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut out = vec![0u8; msg.len() + AUTH_TAG_BYTES];
+        self.seal(&mut out, ad, msg)?;
+        Ok(out)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn open_to_vec(&mut self, ad: Option<&[u8]>, ct: &[u8]) -> Result<Vec<u8>, Error> {
+        let ct_len = ct
+            .len()
+            .checked_sub(AUTH_TAG_BYTES)
+            .ok_or(Error::InvalidBufferLength)?;
+        let mut out = vec![0u8; ct_len];
+        self.open(&mut out, ad, ct)?;
+        Ok(out)
+    }
+}
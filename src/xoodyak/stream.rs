@@ -0,0 +1,256 @@
+use super::*;
+
+pub const STREAM_DEFAULT_CHUNK_BYTES: usize = 65536;
+
+#[derive(Clone, Debug)]
+pub(crate) struct ChunkKeys {
+    base: XoodyakKeyed,
+    chunk_bytes: usize,
+}
+
+impl ChunkKeys {
+    pub(crate) fn new(
+        key: &[u8],
+        nonce: &[u8],
+        ad: Option<&[u8]>,
+        chunk_bytes: usize,
+    ) -> Result<Self, Error> {
+        if chunk_bytes == 0 {
+            return Err(Error::InvalidChunkSize);
+        }
+        let mut base = XoodyakKeyed::new(key, Some(nonce), None, None)?;
+        base.absorb(ad.unwrap_or_default());
+        Ok(ChunkKeys { base, chunk_bytes })
+    }
+
+    #[inline]
+    pub(crate) fn chunk_bytes(&self) -> usize {
+        self.chunk_bytes
+    }
+
+    pub(crate) fn chunk_state(&self, index: u64, last: bool) -> XoodyakKeyed {
+        let mut chunk_id = [0u8; 9];
+        chunk_id[..8].copy_from_slice(&index.to_le_bytes());
+        chunk_id[8] = last as u8;
+        let mut st = self.base.clone();
+        st.absorb(&chunk_id);
+        st
+    }
+
+    #[inline]
+    fn check_chunk_len(&self, len: usize, last: bool) -> Result<(), Error> {
+        if len > self.chunk_bytes || (!last && len != self.chunk_bytes) {
+            return Err(Error::InvalidBufferLength);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn open_chunk_detached(
+        &self,
+        index: u64,
+        in_out: &mut [u8],
+        auth_tag: &Tag,
+        last: bool,
+    ) -> Result<(), Error> {
+        self.check_chunk_len(in_out.len(), last)?;
+        self.chunk_state(index, last)
+            .aead_decrypt_in_place_detached(in_out, auth_tag)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct XoodyakStreamEncryptor {
+    keys: ChunkKeys,
+    index: u64,
+    finished: bool,
+}
+
+impl XoodyakStreamEncryptor {
+    pub fn new(
+        key: &[u8],
+        nonce: &[u8],
+        ad: Option<&[u8]>,
+        chunk_bytes: usize,
+    ) -> Result<Self, Error> {
+        Ok(XoodyakStreamEncryptor {
+            keys: ChunkKeys::new(key, nonce, ad, chunk_bytes)?,
+            index: 0,
+            finished: false,
+        })
+    }
+
+    #[inline]
+    pub fn chunk_bytes(&self) -> usize {
+        self.keys.chunk_bytes()
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn seal_chunk_detached(&mut self, in_out: &mut [u8], last: bool) -> Result<Tag, Error> {
+        if self.finished {
+            return Err(Error::Finalized);
+        }
+        self.keys.check_chunk_len(in_out.len(), last)?;
+        let next_index = self.index.checked_add(1).ok_or(Error::Finalized)?;
+        let auth_tag = self
+            .keys
+            .chunk_state(self.index, last)
+            .aead_encrypt_in_place_detached(in_out);
+        self.index = next_index;
+        self.finished = last;
+        Ok(auth_tag)
+    }
+
+    pub fn seal_chunk(&mut self, in_out: &mut [u8], last: bool) -> Result<(), Error> {
+        let ct_len = in_out
+            .len()
+            .checked_sub(AUTH_TAG_BYTES)
+            .ok_or(Error::InvalidBufferLength)?;
+        let auth_tag = self.seal_chunk_detached(&mut in_out[..ct_len], last)?;
+        in_out[ct_len..].copy_from_slice(auth_tag.as_ref());
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct XoodyakStreamDecryptor {
+    keys: ChunkKeys,
+    index: u64,
+    finished: bool,
+}
+
+impl XoodyakStreamDecryptor {
+    pub fn new(
+        key: &[u8],
+        nonce: &[u8],
+        ad: Option<&[u8]>,
+        chunk_bytes: usize,
+    ) -> Result<Self, Error> {
+        Ok(XoodyakStreamDecryptor {
+            keys: ChunkKeys::new(key, nonce, ad, chunk_bytes)?,
+            index: 0,
+            finished: false,
+        })
+    }
+
+    #[inline]
+    pub fn chunk_bytes(&self) -> usize {
+        self.keys.chunk_bytes()
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn open_chunk_detached(
+        &mut self,
+        in_out: &mut [u8],
+        auth_tag: &Tag,
+        last: bool,
+    ) -> Result<(), Error> {
+        if self.finished {
+            return Err(Error::Finalized);
+        }
+        let next_index = self.index.checked_add(1).ok_or(Error::Finalized)?;
+        self.keys
+            .open_chunk_detached(self.index, in_out, auth_tag, last)?;
+        self.index = next_index;
+        self.finished = last;
+        Ok(())
+    }
+
+    pub fn open_chunk<'t>(
+        &mut self,
+        in_out: &'t mut [u8],
+        last: bool,
+    ) -> Result<&'t mut [u8], Error> {
+        let ct_len = in_out
+            .len()
+            .checked_sub(AUTH_TAG_BYTES)
+            .ok_or(Error::InvalidBufferLength)?;
+        let mut auth_tag_bin = [0u8; AUTH_TAG_BYTES];
+        auth_tag_bin.copy_from_slice(&in_out[ct_len..]);
+        let ct = &mut in_out[..ct_len];
+        self.open_chunk_detached(ct, &Tag::from(auth_tag_bin), last)?;
+        Ok(ct)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct XoodyakStreamRandomAccess {
+    keys: ChunkKeys,
+    chunk_count: u64,
+    ciphertext_len: u64,
+}
+
+impl XoodyakStreamRandomAccess {
+    pub fn new(
+        key: &[u8],
+        nonce: &[u8],
+        ad: Option<&[u8]>,
+        chunk_bytes: usize,
+        ciphertext_len: u64,
+    ) -> Result<Self, Error> {
+        let keys = ChunkKeys::new(key, nonce, ad, chunk_bytes)?;
+        let chunk_ct_bytes = (chunk_bytes + AUTH_TAG_BYTES) as u64;
+        let chunk_count = core::cmp::max(1, ciphertext_len.div_ceil(chunk_ct_bytes));
+        let last_ct_len = ciphertext_len - (chunk_count - 1) * chunk_ct_bytes;
+        if last_ct_len < AUTH_TAG_BYTES as u64 {
+            return Err(Error::InvalidBufferLength);
+        }
+        Ok(XoodyakStreamRandomAccess {
+            keys,
+            chunk_count,
+            ciphertext_len,
+        })
+    }
+
+    #[inline]
+    pub fn chunk_bytes(&self) -> usize {
+        self.keys.chunk_bytes()
+    }
+
+    #[inline]
+    pub fn chunk_count(&self) -> u64 {
+        self.chunk_count
+    }
+
+    #[inline]
+    pub fn plaintext_len(&self) -> u64 {
+        self.ciphertext_len - self.chunk_count * AUTH_TAG_BYTES as u64
+    }
+
+    #[inline]
+    pub fn chunk_index(&self, offset: u64) -> u64 {
+        offset / self.chunk_bytes() as u64
+    }
+
+    pub fn chunk_ciphertext_range(&self, index: u64) -> Result<(u64, usize), Error> {
+        if index >= self.chunk_count {
+            return Err(Error::InvalidChunkIndex);
+        }
+        let chunk_ct_bytes = (self.chunk_bytes() + AUTH_TAG_BYTES) as u64;
+        let offset = index * chunk_ct_bytes;
+        let len = core::cmp::min(chunk_ct_bytes, self.ciphertext_len - offset);
+        Ok((offset, len as usize))
+    }
+
+    pub fn open_chunk<'t>(&self, index: u64, in_out: &'t mut [u8]) -> Result<&'t mut [u8], Error> {
+        let (_, len) = self.chunk_ciphertext_range(index)?;
+        if in_out.len() != len {
+            return Err(Error::InvalidBufferLength);
+        }
+        let ct_len = len - AUTH_TAG_BYTES;
+        let mut auth_tag_bin = [0u8; AUTH_TAG_BYTES];
+        auth_tag_bin.copy_from_slice(&in_out[ct_len..]);
+        let ct = &mut in_out[..ct_len];
+        let last = index + 1 == self.chunk_count;
+        self.keys
+            .open_chunk_detached(index, ct, &Tag::from(auth_tag_bin), last)?;
+        Ok(ct)
+    }
+}
@@ -0,0 +1,50 @@
+use zeroize::Zeroize;
+
+use super::*;
+
+#[derive(Clone, Debug)]
+pub struct XoodyakXofReader<T: XoodyakCommon = XoodyakHash> {
+    inner: T,
+    block: [u8; MAX_STATE_BYTES],
+    offset: usize,
+    started: bool,
+}
+
+impl<T: XoodyakCommon> XoodyakXofReader<T> {
+    pub fn new(inner: T) -> Self {
+        let rate = inner.squeeze_rate();
+        XoodyakXofReader {
+            inner,
+            block: [0u8; MAX_STATE_BYTES],
+            offset: rate,
+            started: false,
+        }
+    }
+
+    pub fn read(&mut self, out: &mut [u8]) {
+        let rate = self.inner.squeeze_rate();
+        let mut out = out;
+        while !out.is_empty() {
+            if self.offset == rate {
+                if self.started {
+                    self.inner.squeeze_more(&mut self.block[..rate]);
+                } else {
+                    self.inner.squeeze(&mut self.block[..rate]);
+                    self.started = true;
+                }
+                self.offset = 0;
+            }
+            let len = out.len().min(rate - self.offset);
+            let (chunk, rest) = out.split_at_mut(len);
+            chunk.copy_from_slice(&self.block[self.offset..self.offset + len]);
+            self.offset += len;
+            out = rest;
+        }
+    }
+}
+
+impl<T: XoodyakCommon> Drop for XoodyakXofReader<T> {
+    fn drop(&mut self) {
+        self.block.zeroize();
+    }
+}
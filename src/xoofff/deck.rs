@@ -0,0 +1,122 @@
+use zeroize::Zeroize;
+
+use super::*;
+
+pub const BLOCK_BYTES: usize = 48;
+pub const MAX_KEY_BYTES: usize = BLOCK_BYTES - 1;
+
+pub(crate) const LANES: usize = 16;
+
+#[derive(Clone, Debug)]
+pub struct Xoofff {
+    key: Xoodoo,
+    key_roll: Xoodoo,
+    accu: Xoodoo,
+}
+
+impl Xoofff {
+    pub fn new(key: &[u8]) -> Result<Self, Error> {
+        if key.len() > MAX_KEY_BYTES {
+            return Err(Error::InvalidParameterLength);
+        }
+        let mut padded = [0u8; BLOCK_BYTES];
+        padded[..key.len()].copy_from_slice(key);
+        padded[key.len()] = 0x01;
+        let mut masked_key = Xoodoo::from_bytes(padded);
+        padded.zeroize();
+        masked_key.permute_rounds(ROUNDS);
+        Ok(Xoofff {
+            key_roll: masked_key.clone(),
+            key: masked_key,
+            accu: Xoodoo::default(),
+        })
+    }
+
+    pub fn restart(&mut self) {
+        self.key_roll = self.key.clone();
+        self.accu = Xoodoo::default();
+    }
+
+    #[inline]
+    fn compress_block(&mut self, block: &[u8]) {
+        let mut st = self.key_roll.clone();
+        st.add_bytes(block);
+        st.permute_rounds(ROUNDS);
+        self.accu.add_state(&st);
+        self.key_roll.roll_xc();
+    }
+
+    pub fn compress(&mut self, input: &[u8]) {
+        self.compress_with_suffix(input, 0x01)
+    }
+
+    pub(crate) fn compress_blocks(&mut self, blocks: &[u8]) {
+        debug_assert_eq!(blocks.len() % BLOCK_BYTES, 0);
+        let mut batches = blocks.chunks_exact(LANES * BLOCK_BYTES);
+        for batch in &mut batches {
+            let mut multi = XoodooMulti::<LANES>::new();
+            for (i, block) in batch.chunks_exact(BLOCK_BYTES).enumerate() {
+                multi.set_state(i, &self.key_roll);
+                multi.add_bytes(i, block);
+                self.key_roll.roll_xc();
+            }
+            multi.permute_rounds(ROUNDS);
+            multi.accumulate_into(&mut self.accu);
+        }
+        for block in batches.remainder().chunks_exact(BLOCK_BYTES) {
+            self.compress_block(block);
+        }
+    }
+
+    pub(crate) fn compress_last(&mut self, rem: &[u8], suffix: u8) {
+        let mut last = [0u8; BLOCK_BYTES];
+        last[..rem.len()].copy_from_slice(rem);
+        last[rem.len()] = suffix;
+        self.compress_block(&last);
+        last.zeroize();
+        self.key_roll.roll_xc();
+    }
+
+    pub(crate) fn compress_with_suffix(&mut self, input: &[u8], suffix: u8) {
+        let (blocks, rem) = input.split_at(input.len() - input.len() % BLOCK_BYTES);
+        self.compress_blocks(blocks);
+        self.compress_last(rem, suffix);
+    }
+
+    pub fn expand(&self, out: &mut [u8], offset: usize) {
+        out.iter_mut().for_each(|x| *x = 0);
+        self.expand_xor(out, offset)
+    }
+
+    pub(crate) fn expand_xor(&self, in_out: &mut [u8], offset: usize) {
+        let mut y = self.accu.clone();
+        y.permute_rounds(ROUNDS);
+        for _ in 0..offset / BLOCK_BYTES {
+            y.roll_xe();
+        }
+        let mut skip = offset % BLOCK_BYTES;
+        let mut block = [0u8; BLOCK_BYTES];
+        let mut in_out = in_out;
+        while !in_out.is_empty() {
+            let mut st = y.clone();
+            st.permute_rounds(ROUNDS);
+            st.add_state(&self.key_roll);
+            st.extract_bytes(&mut block);
+            let len = in_out.len().min(BLOCK_BYTES - skip);
+            for (x, k) in in_out[..len].iter_mut().zip(&block[skip..skip + len]) {
+                *x ^= k;
+            }
+            in_out = &mut in_out[len..];
+            skip = 0;
+            y.roll_xe();
+        }
+        block.zeroize();
+    }
+
+    #[cfg(feature = "std")]
+    pub fn expand_to_vec(&self, out_len: usize, offset: usize) -> Vec<u8> {
+        let mut out = vec![0u8; out_len];
+        self.expand(&mut out, offset);
+        out
+    }
+}
@@ -0,0 +1,76 @@
+use zeroize::Zeroize;
+
+use super::*;
+
+const BATCH_BYTES: usize = LANES * BLOCK_BYTES;
+
+#[derive(Clone, Debug)]
+struct Pending {
+    buf: [u8; BATCH_BYTES],
+    len: usize,
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        self.buf.zeroize();
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct XoofffMac {
+    deck: Xoofff,
+    pending: Pending,
+}
+
+impl XoofffMac {
+    pub fn new(key: &[u8]) -> Result<Self, Error> {
+        Ok(XoofffMac {
+            deck: Xoofff::new(key)?,
+            pending: Pending {
+                buf: [0u8; BATCH_BYTES],
+                len: 0,
+            },
+        })
+    }
+
+    pub fn update(&mut self, bin: &[u8]) {
+        let mut bin = bin;
+        if self.pending.len > 0 {
+            let len = bin.len().min(BATCH_BYTES - self.pending.len);
+            self.pending.buf[self.pending.len..self.pending.len + len].copy_from_slice(&bin[..len]);
+            self.pending.len += len;
+            bin = &bin[len..];
+            if self.pending.len < BATCH_BYTES {
+                return;
+            }
+            self.deck.compress_blocks(&self.pending.buf);
+            self.pending.len = 0;
+        }
+        let (batches, rest) = bin.split_at(bin.len() - bin.len() % BATCH_BYTES);
+        self.deck.compress_blocks(batches);
+        self.pending.buf[..rest.len()].copy_from_slice(rest);
+        self.pending.len = rest.len();
+    }
+
+    pub fn finalize_into(mut self, out: &mut [u8]) {
+        let pending = &self.pending.buf[..self.pending.len];
+        let (blocks, rem) = pending.split_at(pending.len() - pending.len() % BLOCK_BYTES);
+        self.deck.compress_blocks(blocks);
+        self.deck.compress_last(rem, 0x01);
+        self.deck.expand(out, 0);
+    }
+
+    pub fn finalize(self) -> Tag {
+        let mut auth_tag = Tag::default();
+        self.finalize_into(auth_tag.inner_mut());
+        auth_tag
+    }
+
+    pub fn verify(self, auth_tag: &Tag) -> Result<(), Error> {
+        if self.finalize() == *auth_tag {
+            Ok(())
+        } else {
+            Err(Error::TagMismatch)
+        }
+    }
+}
@@ -0,0 +1,17 @@
+mod deck;
+mod mac;
+mod sane;
+mod sanse;
+mod wbc;
+
+pub use deck::*;
+pub use mac::*;
+pub use sane::*;
+pub use sanse::*;
+pub use wbc::*;
+
+use crate::error::*;
+use crate::xoodoo::*;
+use crate::xoodyak::{Tag, AUTH_TAG_BYTES};
+
+pub(crate) const ROUNDS: usize = 6;
@@ -0,0 +1,112 @@
+use super::*;
+
+#[derive(Clone, Debug)]
+pub struct XoofffSane {
+    deck: Xoofff,
+    e: u8,
+    messages: u64,
+    desynchronized: bool,
+}
+
+impl XoofffSane {
+    pub fn new(key: &[u8], nonce: &[u8]) -> Result<(Self, Tag), Error> {
+        let mut deck = Xoofff::new(key)?;
+        deck.compress(nonce);
+        let mut auth_tag = Tag::default();
+        deck.expand(auth_tag.inner_mut(), 0);
+        let sane = XoofffSane {
+            deck,
+            e: 0,
+            messages: 0,
+            desynchronized: false,
+        };
+        Ok((sane, auth_tag))
+    }
+
+    #[inline]
+    pub fn messages(&self) -> u64 {
+        self.messages
+    }
+
+    #[inline]
+    pub fn is_desynchronized(&self) -> bool {
+        self.desynchronized
+    }
+
+    #[inline]
+    fn check(&self) -> Result<(), Error> {
+        if self.desynchronized {
+            return Err(Error::Desynchronized);
+        }
+        Ok(())
+    }
+
+    fn absorb_message(&self, deck: &mut Xoofff, ad: &[u8], ct: &[u8]) {
+        if !ad.is_empty() || ct.is_empty() {
+            deck.compress_with_suffix(ad, 0x04 | (self.e << 1));
+        }
+        if !ct.is_empty() {
+            deck.compress_with_suffix(ct, 0x05 | (self.e << 1));
+        }
+    }
+
+    pub fn wrap_in_place(&mut self, in_out: &mut [u8], ad: Option<&[u8]>) -> Result<Tag, Error> {
+        self.check()?;
+        self.deck.expand_xor(in_out, AUTH_TAG_BYTES);
+        let mut deck = self.deck.clone();
+        self.absorb_message(&mut deck, ad.unwrap_or_default(), in_out);
+        let mut auth_tag = Tag::default();
+        deck.expand(auth_tag.inner_mut(), 0);
+        self.deck = deck;
+        self.e ^= 1;
+        self.messages += 1;
+        Ok(auth_tag)
+    }
+
+    pub fn wrap(&mut self, out: &mut [u8], ad: Option<&[u8]>, msg: &[u8]) -> Result<Tag, Error> {
+        if out.len() < msg.len() {
+            return Err(Error::InvalidBufferLength);
+        }
+        let out = &mut out[..msg.len()];
+        out.copy_from_slice(msg);
+        self.wrap_in_place(out, ad)
+    }
+
+    pub fn unwrap_in_place(
+        &mut self,
+        in_out: &mut [u8],
+        auth_tag: &Tag,
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        self.check()?;
+        let mut deck = self.deck.clone();
+        self.absorb_message(&mut deck, ad.unwrap_or_default(), in_out);
+        let mut computed_tag = Tag::default();
+        deck.expand(computed_tag.inner_mut(), 0);
+        if computed_tag != *auth_tag {
+            in_out.iter_mut().for_each(|x| *x = 0);
+            self.desynchronized = true;
+            return Err(Error::TagMismatch);
+        }
+        self.deck.expand_xor(in_out, AUTH_TAG_BYTES);
+        self.deck = deck;
+        self.e ^= 1;
+        self.messages += 1;
+        Ok(())
+    }
+
+    pub fn unwrap(
+        &mut self,
+        out: &mut [u8],
+        auth_tag: &Tag,
+        ad: Option<&[u8]>,
+        ct: &[u8],
+    ) -> Result<(), Error> {
+        if out.len() < ct.len() {
+            return Err(Error::InvalidBufferLength);
+        }
+        let out = &mut out[..ct.len()];
+        out.copy_from_slice(ct);
+        self.unwrap_in_place(out, auth_tag, ad)
+    }
+}
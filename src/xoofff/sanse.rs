@@ -0,0 +1,121 @@
+use super::*;
+
+#[derive(Clone, Debug)]
+pub struct XoofffSanse {
+    deck: Xoofff,
+    e: u8,
+    messages: u64,
+    desynchronized: bool,
+}
+
+impl XoofffSanse {
+    pub fn new(key: &[u8]) -> Result<Self, Error> {
+        Ok(XoofffSanse {
+            deck: Xoofff::new(key)?,
+            e: 0,
+            messages: 0,
+            desynchronized: false,
+        })
+    }
+
+    #[inline]
+    pub fn messages(&self) -> u64 {
+        self.messages
+    }
+
+    #[inline]
+    pub fn is_desynchronized(&self) -> bool {
+        self.desynchronized
+    }
+
+    #[inline]
+    fn check(&self) -> Result<(), Error> {
+        if self.desynchronized {
+            return Err(Error::Desynchronized);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn absorb_ad(&self, deck: &mut Xoofff, ad: &[u8], msg_len: usize) {
+        if !ad.is_empty() || msg_len == 0 {
+            deck.compress_with_suffix(ad, 0x04 | (self.e << 1));
+        }
+    }
+
+    #[inline]
+    fn keystream(&self, deck: &Xoofff, auth_tag: &Tag, in_out: &mut [u8]) {
+        let mut deck = deck.clone();
+        deck.compress_with_suffix(auth_tag.as_ref(), 0x0b | (self.e << 2));
+        deck.expand_xor(in_out, 0);
+    }
+
+    pub fn wrap_in_place(&mut self, in_out: &mut [u8], ad: Option<&[u8]>) -> Result<Tag, Error> {
+        self.check()?;
+        let mut deck = self.deck.clone();
+        self.absorb_ad(&mut deck, ad.unwrap_or_default(), in_out.len());
+        let mut auth_tag = Tag::default();
+        if in_out.is_empty() {
+            deck.expand(auth_tag.inner_mut(), 0);
+        } else {
+            let history = deck.clone();
+            deck.compress_with_suffix(in_out, 0x0a | (self.e << 2));
+            deck.expand(auth_tag.inner_mut(), 0);
+            self.keystream(&history, &auth_tag, in_out);
+        }
+        self.deck = deck;
+        self.e ^= 1;
+        self.messages += 1;
+        Ok(auth_tag)
+    }
+
+    pub fn wrap(&mut self, out: &mut [u8], ad: Option<&[u8]>, msg: &[u8]) -> Result<Tag, Error> {
+        if out.len() < msg.len() {
+            return Err(Error::InvalidBufferLength);
+        }
+        let out = &mut out[..msg.len()];
+        out.copy_from_slice(msg);
+        self.wrap_in_place(out, ad)
+    }
+
+    pub fn unwrap_in_place(
+        &mut self,
+        in_out: &mut [u8],
+        auth_tag: &Tag,
+        ad: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        self.check()?;
+        let mut deck = self.deck.clone();
+        self.absorb_ad(&mut deck, ad.unwrap_or_default(), in_out.len());
+        if !in_out.is_empty() {
+            self.keystream(&deck, auth_tag, in_out);
+            deck.compress_with_suffix(in_out, 0x0a | (self.e << 2));
+        }
+        let mut computed_tag = Tag::default();
+        deck.expand(computed_tag.inner_mut(), 0);
+        if computed_tag != *auth_tag {
+            in_out.iter_mut().for_each(|x| *x = 0);
+            self.desynchronized = true;
+            return Err(Error::TagMismatch);
+        }
+        self.deck = deck;
+        self.e ^= 1;
+        self.messages += 1;
+        Ok(())
+    }
+
+    pub fn unwrap(
+        &mut self,
+        out: &mut [u8],
+        auth_tag: &Tag,
+        ad: Option<&[u8]>,
+        ct: &[u8],
+    ) -> Result<(), Error> {
+        if out.len() < ct.len() {
+            return Err(Error::InvalidBufferLength);
+        }
+        let out = &mut out[..ct.len()];
+        out.copy_from_slice(ct);
+        self.unwrap_in_place(out, auth_tag, ad)
+    }
+}
@@ -0,0 +1,131 @@
+use super::*;
+
+const MASKED_BYTES: usize = 2 * BLOCK_BYTES;
+
+#[derive(Clone, Debug)]
+pub struct XoofffWbc {
+    deck: Xoofff,
+}
+
+impl XoofffWbc {
+    pub fn new(key: &[u8]) -> Result<Self, Error> {
+        Ok(XoofffWbc {
+            deck: Xoofff::new(key)?,
+        })
+    }
+
+    fn split(len: usize) -> usize {
+        if 8 * len <= 2 * 8 * BLOCK_BYTES - 10 {
+            return len.div_ceil(2);
+        }
+        let q = (8 * len + 9) / (8 * BLOCK_BYTES) + 1;
+        let x = usize::BITS - 1 - (q - 1).leading_zeros();
+        (q - (1 << x)) * BLOCK_BYTES - 1
+    }
+
+    #[inline]
+    fn h(&self, input: &[u8], suffix: u8, out: &mut [u8]) {
+        let mut deck = self.deck.clone();
+        deck.compress_with_suffix(input, suffix);
+        let len = out.len().min(MASKED_BYTES);
+        deck.expand_xor(&mut out[..len], 0);
+    }
+
+    #[inline]
+    fn g(tweaked: &Xoofff, input: &[u8], suffix: u8, out: &mut [u8]) {
+        let mut deck = tweaked.clone();
+        deck.compress_with_suffix(input, suffix);
+        deck.expand_xor(out, 0);
+    }
+
+    pub fn encrypt(&self, tweak: &[u8], in_out: &mut [u8]) {
+        let mut tweaked = self.deck.clone();
+        tweaked.compress(tweak);
+        let (left, right) = in_out.split_at_mut(Self::split(in_out.len()));
+        self.h(left, 0x02, right);
+        Self::g(&tweaked, right, 0x03, left);
+        Self::g(&tweaked, left, 0x02, right);
+        self.h(right, 0x03, left);
+    }
+
+    pub fn decrypt(&self, tweak: &[u8], in_out: &mut [u8]) {
+        let mut tweaked = self.deck.clone();
+        tweaked.compress(tweak);
+        let (left, right) = in_out.split_at_mut(Self::split(in_out.len()));
+        self.h(right, 0x03, left);
+        Self::g(&tweaked, left, 0x02, right);
+        Self::g(&tweaked, right, 0x03, left);
+        self.h(left, 0x02, right);
+    }
+
+    pub fn encrypt_sector<const N: usize>(&self, tweak: u64, sector: &mut [u8; N]) {
+        self.encrypt(&tweak.to_le_bytes(), sector)
+    }
+
+    pub fn decrypt_sector<const N: usize>(&self, tweak: u64, sector: &mut [u8; N]) {
+        self.decrypt(&tweak.to_le_bytes(), sector)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct XoofffWbcAe {
+    wbc: XoofffWbc,
+}
+
+impl XoofffWbcAe {
+    pub fn new(key: &[u8]) -> Result<Self, Error> {
+        Ok(XoofffWbcAe {
+            wbc: XoofffWbc::new(key)?,
+        })
+    }
+
+    pub fn encrypt_in_place(&self, in_out: &mut [u8], ad: Option<&[u8]>) -> Result<(), Error> {
+        let msg_len = in_out
+            .len()
+            .checked_sub(AUTH_TAG_BYTES)
+            .ok_or(Error::InvalidBufferLength)?;
+        in_out[msg_len..].iter_mut().for_each(|x| *x = 0);
+        self.wbc.encrypt(ad.unwrap_or_default(), in_out);
+        Ok(())
+    }
+
+    pub fn decrypt_in_place<'t>(
+        &self,
+        in_out: &'t mut [u8],
+        ad: Option<&[u8]>,
+    ) -> Result<&'t mut [u8], Error> {
+        let msg_len = in_out
+            .len()
+            .checked_sub(AUTH_TAG_BYTES)
+            .ok_or(Error::InvalidBufferLength)?;
+        self.wbc.decrypt(ad.unwrap_or_default(), in_out);
+        if in_out[msg_len..].iter().fold(0, |c, x| c | x) != 0 {
+            in_out.iter_mut().for_each(|x| *x = 0);
+            return Err(Error::TagMismatch);
+        }
+        Ok(&mut in_out[..msg_len])
+    }
+
+    pub fn encrypt(&self, out: &mut [u8], ad: Option<&[u8]>, msg: &[u8]) -> Result<(), Error> {
+        if out.len() != msg.len() + AUTH_TAG_BYTES {
+            return Err(Error::InvalidBufferLength);
+        }
+        out[..msg.len()].copy_from_slice(msg);
+        self.encrypt_in_place(out, ad)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn encrypt_to_vec(&self, ad: Option<&[u8]>, msg: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut out = vec![0u8; msg.len() + AUTH_TAG_BYTES];
+        self.encrypt(&mut out, ad, msg)?;
+        Ok(out)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn decrypt_to_vec(&self, ad: Option<&[u8]>, ct: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut out = ct.to_vec();
+        let msg_len = self.decrypt_in_place(&mut out, ad)?.len();
+        out.truncate(msg_len);
+        Ok(out)
+    }
+}